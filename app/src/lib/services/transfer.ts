@@ -9,6 +9,7 @@ import {
 	getExecutingPoolAccAddress,
 	getCompDefAccAddress
 } from '@arcium-hq/client';
+import { getAssociatedTokenAddressSync } from '@solana/spl-token';
 import type { Program } from '@coral-xyz/anchor';
 import type { DegenCash } from '../anchor/types/degen_cash';
 import { PUBLIC_ARCIUM_CLUSTER_PUBKEY } from '$env/static/public';
@@ -55,6 +56,9 @@ export class TransferService {
 			program.programId
 		);
 
+		// USDC vault backing DC, used for NAV
+		const depositAta = getAssociatedTokenAddressSync(depositMint, dcGlobalMintPDA, true);
+
 		// Get computation definition offset
 		const compDefOffset = getCompDefAccOffset('transfer');
 
@@ -83,7 +87,7 @@ export class TransferService {
 				dcGlobalMintAccount: dcGlobalMintPDA,
 				dcUserTokenAccount: senderDcTokenAccount,
 				receiverDcUserTokenAccount: receiverDcTokenAccount,
-				depositMint: depositMint
+				depositAta: depositAta
 			})
 			.signers([senderKeypair])
 			.rpc({ skipPreflight: false, commitment: 'confirmed' });
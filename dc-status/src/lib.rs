@@ -0,0 +1,58 @@
+// Status codes revealed by the circuits in encrypted-ixs
+//
+// Arcis can't resolve paths outside the #[encrypted] module, so the circuits mirror
+// these codes as consts (checked by encrypted-ixs/tests/status.rs). The program
+// decodes the revealed u8 back into DCStatus for its events and receipts.
+//
+// Borsh writes the variant index, not the discriminant, so every variant is declared
+// in code order with its index as its code. Unknown keeps index 7, and new codes are
+// appended after it starting at 8.
+
+#[cfg(feature = "anchor")]
+use anchor_lang::prelude::*;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "anchor",
+    derive(AnchorSerialize, AnchorDeserialize, InitSpace)
+)]
+pub enum DCStatus {
+    Success = 0,
+    MathOverflow = 1,
+    // Balance (or worst-case charge) exceeds the available funds
+    InsufficientFunds = 2,
+    // Couldn't generate a valid random number in the allotted attempts
+    RNGFailure = 3,
+    // Submitted escrow secret doesn't match the committed one
+    SecretMismatch = 4,
+    // Ticket ownership proof or claim doesn't match under the claim's key
+    NotOwner = 5,
+    // Encrypted transfer amount is above DCConfig.max_transfer_amount
+    MaxAmountExceeded = 6,
+    // Code not produced by any circuit
+    Unknown = 7,
+}
+
+impl DCStatus {
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            0 => DCStatus::Success,
+            1 => DCStatus::MathOverflow,
+            2 => DCStatus::InsufficientFunds,
+            3 => DCStatus::RNGFailure,
+            4 => DCStatus::SecretMismatch,
+            5 => DCStatus::NotOwner,
+            6 => DCStatus::MaxAmountExceeded,
+            _ => DCStatus::Unknown,
+        }
+    }
+
+    pub const fn is_success(self) -> bool {
+        matches!(self, DCStatus::Success)
+    }
+}
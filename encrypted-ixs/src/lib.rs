@@ -1,11 +1,40 @@
 use arcis_imports::*;
 
 #[encrypted]
-mod circuits {
+pub mod circuits {
     use arcis_imports::*;
 
     pub struct EmptyStruct;
 
+    // Hash-lock secret submitted by a claimer under their account key, claimer is the
+    // claimer_tag of the signing wallet so a copied ciphertext fails for any other signer
+    pub struct EscrowClaim {
+        pub secret: u128,
+        pub claimer: u128,
+    }
+
+    // Fresh proof of holding the x25519 key a ticket's owner proof is encrypted under,
+    // made out to the claimer_tag of the signing wallet like EscrowClaim
+    pub struct TicketClaim {
+        pub ticket_tag: u64,
+        pub claimer: u128,
+    }
+
+    // Status codes, mirror dc_status::DCStatus (arcis can't resolve paths outside
+    // this module, tests/status.rs keeps them in sync)
+    pub const SUCCESS: u8 = 0;
+    pub const MATH_OVERFLOW: u8 = 1;
+    pub const INSUFFICIENT_FUNDS: u8 = 2;
+    pub const RNG_FAILURE: u8 = 3;
+    pub const SECRET_MISMATCH: u8 = 4;
+    pub const NOT_OWNER: u8 = 5;
+    pub const MAX_AMOUNT_EXCEEDED: u8 = 6;
+
+    // Mirrors LOTTERY_WINNING_TICKETS in the program
+    pub const WINNING_TICKETS: usize = 5;
+    // Rejection sampling attempts per winning ticket
+    const DRAW_ATTEMPTS: usize = 32;
+
     #[instruction]
     pub fn init_global_dc_mint(input_ctxt: Enc<Mxe, EmptyStruct>) -> Enc<Mxe, u64> {
         input_ctxt.owner.from_arcis(0_u64)
@@ -17,9 +46,26 @@ mod circuits {
     }
 
     /**
-     * Status Codes:
-     * 0: Success
-     * 1: Math Overflow
+     * Reveals only whether the balance is zero, so the account can be closed
+     */
+    #[instruction]
+    pub fn close_user_dc_balance(balance_ctxt: Enc<Shared, u64>) -> bool {
+        (balance_ctxt.to_arcis() == 0).reveal()
+    }
+
+    /**
+     * Re-encrypts the balance under the owner's new x25519 key
+     */
+    #[instruction]
+    pub fn rotate_dc_key(
+        balance_ctxt: Enc<Shared, u64>,
+        new_owner_ctxt: Enc<Shared, EmptyStruct>,
+    ) -> Enc<Shared, u64> {
+        new_owner_ctxt.owner.from_arcis(balance_ctxt.to_arcis())
+    }
+
+    /**
+     * Status Codes: SUCCESS, MATH_OVERFLOW
      */
     #[instruction]
     pub fn deposit(
@@ -33,14 +79,14 @@ mod circuits {
         let new_global_mint_amount = global_mint_amount + deposit_amount;
         let new_user_dc_balance = user_dc_balance + deposit_amount;
 
-        let mut status_code = 0_u8;
+        let mut status_code = SUCCESS;
 
         if new_global_mint_amount < global_mint_amount {
-            status_code = 1;
+            status_code = MATH_OVERFLOW;
         }
 
         if new_user_dc_balance < user_dc_balance {
-            status_code = 1;
+            status_code = MATH_OVERFLOW;
         }
 
         (
@@ -54,47 +100,42 @@ mod circuits {
     }
 
     /**
-     * Status Codes:
-     * 0: Success
-     * 1: Math Overflow
-     * 2: Insufficient Funds
+     * Status Codes: SUCCESS, MATH_OVERFLOW, INSUFFICIENT_FUNDS
      */
     #[instruction]
     pub fn withdraw(
         global_mint_amount_ctxt: Enc<Mxe, u64>,
         user_dc_balance_ctxt: Enc<Shared, u64>,
         withdraw_amount: u64,
+        withdraw_fee_bps: u16,
     ) -> (u8, u64, Enc<Mxe, u64>, Enc<Shared, u64>) {
         let global_mint_amount = global_mint_amount_ctxt.to_arcis();
         let user_dc_balance = user_dc_balance_ctxt.to_arcis();
 
-        let mut status_code = 0_u8;
-
-        // Calculate 50 bps (0.5%) withdrawal fee
-        let fee_amount = (withdraw_amount as u128 * 50) / 10000;
+        // Calculate withdrawal fee (DCConfig.withdraw_fee_bps)
+        let fee_amount = (withdraw_amount as u128 * withdraw_fee_bps as u128) / 10000;
         let total_charge = withdraw_amount as u128 + fee_amount;
 
-        // Check if user has enough balance including fee
-        if total_charge > user_dc_balance as u128 {
-            status_code = 2; // Insufficient Funds
-        }
-
-        // Check if global mint has enough
-        if total_charge > global_mint_amount as u128 {
-            status_code = 2; // Insufficient Funds
-        }
-
-        let new_global_mint_amount = global_mint_amount - (total_charge as u64);
-        let new_user_dc_balance = user_dc_balance - (total_charge as u64);
-
-        // Check for underflow
-        if new_global_mint_amount > global_mint_amount {
-            status_code = 1; // Math Overflow
-        }
+        // Checks are mutually exclusive, first failure wins
+        let status_code = if total_charge > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else if total_charge > user_dc_balance as u128
+            || total_charge > global_mint_amount as u128
+        {
+            INSUFFICIENT_FUNDS
+        } else {
+            SUCCESS
+        };
 
-        if new_user_dc_balance > user_dc_balance {
-            status_code = 1; // Math Overflow
-        }
+        // Only subtract on the guarded path, failures re-encrypt the unchanged inputs
+        let (new_global_mint_amount, new_user_dc_balance) = if status_code == SUCCESS {
+            (
+                global_mint_amount - total_charge as u64,
+                user_dc_balance - total_charge as u64,
+            )
+        } else {
+            (global_mint_amount, user_dc_balance)
+        };
 
         (
             status_code.reveal(),
@@ -107,20 +148,18 @@ mod circuits {
     }
 
     /**
-     * Status Codes:
-     * 0: Success
-     * 1: Math Overflow
-     * 2: Insufficient Funds
-     * 3: RNG Failure (couldn't generate valid random number)
+     * Status Codes: SUCCESS, MATH_OVERFLOW, INSUFFICIENT_FUNDS,
+     * RNG_FAILURE (couldn't generate valid random number)
      */
     #[instruction]
     pub fn transfer(
-        global_balance: u64,
+        global_reserves_balance: u64,
         global_dc_balance_ctxt: Enc<Mxe, u64>,
         sender_balance_ctxt: Enc<Shared, u64>,
         receiver_balance_ctxt: Enc<Shared, u64>,
         transfer_amount: u64,
         max_variance: u8,
+        fee_bps: u16,
     ) -> (
         u8,
         u8,
@@ -129,11 +168,104 @@ mod circuits {
         Enc<Mxe, u64>,
         Enc<Shared, u64>,
     ) {
-        let mut status_code = 0_u8;
-        let global_reserves_balance = global_balance;
-        let mut global_dc_balance = global_dc_balance_ctxt.to_arcis();
-        let mut sender_balance = sender_balance_ctxt.to_arcis();
-        let mut receiver_balance = receiver_balance_ctxt.to_arcis();
+        let (status_code, variance_roll, sender_balance, global_dc_balance, receiver_balance) =
+            apply_transfer(
+                global_reserves_balance,
+                global_dc_balance_ctxt.to_arcis(),
+                sender_balance_ctxt.to_arcis(),
+                receiver_balance_ctxt.to_arcis(),
+                transfer_amount,
+                max_variance,
+                fee_bps,
+            );
+
+        (
+            status_code.reveal(),
+            variance_roll.reveal(),
+            transfer_amount.reveal(),
+            sender_balance_ctxt.owner.from_arcis(sender_balance),
+            global_dc_balance_ctxt.owner.from_arcis(global_dc_balance),
+            receiver_balance_ctxt.owner.from_arcis(receiver_balance),
+        )
+    }
+
+    /**
+     * Same as transfer, but the amount is encrypted by the sender so the max amount and
+     * worst-case checks run in MPC. Only the status is revealed, not the amount or variance
+     * Status Codes: SUCCESS, MATH_OVERFLOW, INSUFFICIENT_FUNDS, RNG_FAILURE,
+     * MAX_AMOUNT_EXCEEDED
+     */
+    #[instruction]
+    pub fn confidential_transfer(
+        global_reserves_balance: u64,
+        global_dc_balance_ctxt: Enc<Mxe, u64>,
+        sender_balance_ctxt: Enc<Shared, u64>,
+        receiver_balance_ctxt: Enc<Shared, u64>,
+        transfer_amount_ctxt: Enc<Shared, u64>,
+        max_transfer_amount: u64,
+        max_variance: u8,
+        fee_bps: u16,
+    ) -> (u8, Enc<Shared, u64>, Enc<Mxe, u64>, Enc<Shared, u64>) {
+        let global_dc_balance = global_dc_balance_ctxt.to_arcis();
+        let sender_balance = sender_balance_ctxt.to_arcis();
+        let receiver_balance = receiver_balance_ctxt.to_arcis();
+        let transfer_amount = transfer_amount_ctxt.to_arcis();
+
+        let (transfer_status, _, new_sender_balance, new_global_dc_balance, new_receiver_balance) =
+            apply_transfer(
+                global_reserves_balance,
+                global_dc_balance,
+                sender_balance,
+                receiver_balance,
+                transfer_amount,
+                max_variance,
+                fee_bps,
+            );
+
+        // Both branches run in MPC, an oversized amount keeps every balance unchanged
+        let (status_code, new_sender_balance, new_global_dc_balance, new_receiver_balance) =
+            if transfer_amount > max_transfer_amount {
+                (
+                    MAX_AMOUNT_EXCEEDED,
+                    sender_balance,
+                    global_dc_balance,
+                    receiver_balance,
+                )
+            } else {
+                (
+                    transfer_status,
+                    new_sender_balance,
+                    new_global_dc_balance,
+                    new_receiver_balance,
+                )
+            };
+
+        (
+            status_code.reveal(),
+            sender_balance_ctxt.owner.from_arcis(new_sender_balance),
+            global_dc_balance_ctxt
+                .owner
+                .from_arcis(new_global_dc_balance),
+            receiver_balance_ctxt.owner.from_arcis(new_receiver_balance),
+        )
+    }
+
+    // Variance roll, fee curve, NAV adjustment and worst-case check shared by transfer and
+    // confidential_transfer. Returns (status, variance roll, sender, global DC, receiver),
+    // balances are unchanged unless the status is SUCCESS
+    fn apply_transfer(
+        global_reserves_balance: u64,
+        global_dc_balance: u64,
+        sender_balance: u64,
+        receiver_balance: u64,
+        transfer_amount: u64,
+        max_variance: u8,
+        fee_bps: u16,
+    ) -> (u8, u8, u64, u64, u64) {
+        let mut status_code = SUCCESS;
+        let mut global_dc_balance = global_dc_balance;
+        let mut sender_balance = sender_balance;
+        let mut receiver_balance = receiver_balance;
         let mut actual_variance_roll = 0_u8;
 
         let variance_roll = if max_variance == 0 {
@@ -172,13 +304,13 @@ mod circuits {
             }
 
             if found_valid == 0 {
-                status_code = 3;
+                status_code = RNG_FAILURE;
             }
 
             result
         };
 
-        if status_code == 0 {
+        if status_code == SUCCESS {
             actual_variance_roll = variance_roll as u8;
 
             let variance_bool = ArcisRNG::bool();
@@ -190,11 +322,12 @@ mod circuits {
                 transfer_amount as u128 + variance_adjustment
             };
 
-            let fee_bps = 255_u128 - max_variance as u128;
-            let fee_amount = (transfer_amount as u128 * fee_bps) / 10000_u128;
+            // fee_bps comes from the DCConfig fee curve for this max_variance
+            let fee_amount = (transfer_amount as u128 * fee_bps as u128) / 10000_u128;
 
             let modified_with_fee = modified_transfer + fee_amount;
 
+            // NAV% = (Total DC Supply / USDC Reserves) * 100
             let nav_percent = if global_reserves_balance > 0 {
                 (global_dc_balance as u128 * 100_u128) / global_reserves_balance as u128
             } else {
@@ -218,19 +351,17 @@ mod circuits {
             };
 
             let worst_case_variance = (transfer_amount as u128 * max_variance as u128) / 255_u128;
-            let worst_case_fee =
-                (transfer_amount as u128 * (255_u128 - max_variance as u128)) / 10000_u128;
-            let worst_case_charge = transfer_amount as u128 + worst_case_variance + worst_case_fee;
+            let worst_case_charge = transfer_amount as u128 + worst_case_variance + fee_amount;
 
             if worst_case_charge > sender_balance as u128 {
-                status_code = 2;
+                status_code = INSUFFICIENT_FUNDS;
             }
 
-            if (receiver_balance as u128) + (transfer_amount as u128) < (receiver_balance as u128) {
-                status_code = 1;
+            if (receiver_balance as u128) + (transfer_amount as u128) > u64::MAX as u128 {
+                status_code = MATH_OVERFLOW;
             }
 
-            if status_code == 0 {
+            if status_code == SUCCESS {
                 let final_sender_charge = if modified_with_nav > sender_balance as u128 {
                     sender_balance
                 } else {
@@ -254,13 +385,513 @@ mod circuits {
             }
         }
 
+        (
+            status_code,
+            actual_variance_roll,
+            sender_balance,
+            global_dc_balance,
+            receiver_balance,
+        )
+    }
+
+    /**
+     * Moves amount between two sub-accounts of the same wallet. No variance, fee or NAV
+     * adjustment and the global supply is untouched, only the status is revealed
+     * Status Codes: SUCCESS, INSUFFICIENT_FUNDS, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn sub_account_transfer(
+        sender_balance_ctxt: Enc<Shared, u64>,
+        receiver_balance_ctxt: Enc<Shared, u64>,
+        transfer_amount: u64,
+    ) -> (u8, Enc<Shared, u64>, Enc<Shared, u64>) {
+        let sender_balance = sender_balance_ctxt.to_arcis();
+        let receiver_balance = receiver_balance_ctxt.to_arcis();
+
+        let status_code = if transfer_amount > sender_balance {
+            INSUFFICIENT_FUNDS
+        } else if (receiver_balance as u128) + (transfer_amount as u128) > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else {
+            SUCCESS
+        };
+
+        let (new_sender_balance, new_receiver_balance) = if status_code == SUCCESS {
+            (
+                sender_balance - transfer_amount,
+                receiver_balance + transfer_amount,
+            )
+        } else {
+            (sender_balance, receiver_balance)
+        };
+
         (
             status_code.reveal(),
-            actual_variance_roll.reveal(),
-            transfer_amount.reveal(),
+            sender_balance_ctxt.owner.from_arcis(new_sender_balance),
+            receiver_balance_ctxt.owner.from_arcis(new_receiver_balance),
+        )
+    }
+
+    // Claimable Transfers
+
+    /**
+     * Same as transfer, but amount goes into an MXE owned pending claim instead of a
+     * receiver balance, held until the recipient opens a DC account and claims it.
+     * The sender pays the same variance, fee and NAV adjustment as a transfer
+     * Status Codes: SUCCESS, INSUFFICIENT_FUNDS, RNG_FAILURE
+     */
+    #[instruction]
+    pub fn send_claimable_transfer(
+        global_reserves_balance: u64,
+        global_dc_balance_ctxt: Enc<Mxe, u64>,
+        sender_balance_ctxt: Enc<Shared, u64>,
+        transfer_amount: u64,
+        max_variance: u8,
+        fee_bps: u16,
+    ) -> (u8, u8, Enc<Shared, u64>, Enc<Mxe, u64>, Enc<Mxe, u64>) {
+        let (status_code, variance_roll, sender_balance, global_dc_balance, claim_amount) =
+            apply_transfer(
+                global_reserves_balance,
+                global_dc_balance_ctxt.to_arcis(),
+                sender_balance_ctxt.to_arcis(),
+                0,
+                transfer_amount,
+                max_variance,
+                fee_bps,
+            );
+
+        (
+            status_code.reveal(),
+            variance_roll.reveal(),
             sender_balance_ctxt.owner.from_arcis(sender_balance),
             global_dc_balance_ctxt.owner.from_arcis(global_dc_balance),
-            receiver_balance_ctxt.owner.from_arcis(receiver_balance),
+            Mxe::get().from_arcis(claim_amount),
+        )
+    }
+
+    /**
+     * Credits the whole pending claim to the claimer, the recipient or the sender once
+     * the claim has expired
+     * Status Codes: SUCCESS, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn claim_transfer(
+        claim_amount_ctxt: Enc<Mxe, u64>,
+        claimer_balance_ctxt: Enc<Shared, u64>,
+    ) -> (u8, Enc<Shared, u64>) {
+        let claim_amount = claim_amount_ctxt.to_arcis();
+        let claimer_balance = claimer_balance_ctxt.to_arcis();
+
+        let new_total = claimer_balance as u128 + claim_amount as u128;
+        let status_code = if new_total > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else {
+            SUCCESS
+        };
+
+        let new_claimer_balance = if status_code == SUCCESS {
+            claimer_balance + claim_amount
+        } else {
+            claimer_balance
+        };
+
+        (
+            status_code.reveal(),
+            claimer_balance_ctxt.owner.from_arcis(new_claimer_balance),
+        )
+    }
+
+    // Escrow
+
+    /**
+     * Moves amount from the creator's balance into a new MXE owned escrow balance
+     * Status Codes: SUCCESS, INSUFFICIENT_FUNDS
+     */
+    #[instruction]
+    pub fn create_escrow(
+        creator_balance_ctxt: Enc<Shared, u64>,
+        amount: u64,
+    ) -> (u8, Enc<Shared, u64>, Enc<Mxe, u64>) {
+        let creator_balance = creator_balance_ctxt.to_arcis();
+
+        let status_code = if amount > creator_balance {
+            INSUFFICIENT_FUNDS
+        } else {
+            SUCCESS
+        };
+
+        let (new_creator_balance, escrow_amount) = if status_code == SUCCESS {
+            (creator_balance - amount, amount)
+        } else {
+            (creator_balance, 0_u64)
+        };
+
+        (
+            status_code.reveal(),
+            creator_balance_ctxt.owner.from_arcis(new_creator_balance),
+            Mxe::get().from_arcis(escrow_amount),
+        )
+    }
+
+    /**
+     * Credits the whole escrow balance to the beneficiary
+     * Status Codes: SUCCESS, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn release_escrow(
+        escrow_amount_ctxt: Enc<Mxe, u64>,
+        beneficiary_balance_ctxt: Enc<Shared, u64>,
+    ) -> (u8, Enc<Shared, u64>) {
+        let escrow_amount = escrow_amount_ctxt.to_arcis();
+        let beneficiary_balance = beneficiary_balance_ctxt.to_arcis();
+
+        let new_total = beneficiary_balance as u128 + escrow_amount as u128;
+        let status_code = if new_total > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else {
+            SUCCESS
+        };
+
+        let new_beneficiary_balance = if status_code == SUCCESS {
+            beneficiary_balance + escrow_amount
+        } else {
+            beneficiary_balance
+        };
+
+        (
+            status_code.reveal(),
+            beneficiary_balance_ctxt
+                .owner
+                .from_arcis(new_beneficiary_balance),
+        )
+    }
+
+    /**
+     * Credits the whole escrow balance to the claimer if their secret matches the
+     * one committed by the creator and the claim was made for the signing wallet.
+     * Neither secret is revealed
+     * Status Codes: SUCCESS, SECRET_MISMATCH, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn claim_escrow(
+        escrow_amount_ctxt: Enc<Mxe, u64>,
+        escrow_secret_ctxt: Enc<Shared, u128>,
+        claim_ctxt: Enc<Shared, EscrowClaim>,
+        claimer_balance_ctxt: Enc<Shared, u64>,
+        claimer: u128,
+    ) -> (u8, Enc<Shared, u64>) {
+        let escrow_amount = escrow_amount_ctxt.to_arcis();
+        let escrow_secret = escrow_secret_ctxt.to_arcis();
+        let claim = claim_ctxt.to_arcis();
+        let claimer_balance = claimer_balance_ctxt.to_arcis();
+
+        let new_total = claimer_balance as u128 + escrow_amount as u128;
+        let status_code = if claim.secret != escrow_secret || claim.claimer != claimer {
+            SECRET_MISMATCH
+        } else if new_total > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else {
+            SUCCESS
+        };
+
+        let new_claimer_balance = if status_code == SUCCESS {
+            claimer_balance + escrow_amount
+        } else {
+            claimer_balance
+        };
+
+        (
+            status_code.reveal(),
+            claimer_balance_ctxt.owner.from_arcis(new_claimer_balance),
+        )
+    }
+
+    /**
+     * Burns an expired escrow by removing its balance from the global supply
+     * Status Codes: SUCCESS, INSUFFICIENT_FUNDS
+     */
+    #[instruction]
+    pub fn expire_escrow(
+        escrow_amount_ctxt: Enc<Mxe, u64>,
+        global_mint_amount_ctxt: Enc<Mxe, u64>,
+    ) -> (u8, Enc<Mxe, u64>) {
+        let escrow_amount = escrow_amount_ctxt.to_arcis();
+        let global_mint_amount = global_mint_amount_ctxt.to_arcis();
+
+        // Can't happen while supply tracks every DC balance, checked anyway
+        let status_code = if escrow_amount > global_mint_amount {
+            INSUFFICIENT_FUNDS
+        } else {
+            SUCCESS
+        };
+
+        let new_global_mint_amount = if status_code == SUCCESS {
+            global_mint_amount - escrow_amount
+        } else {
+            global_mint_amount
+        };
+
+        (
+            status_code.reveal(),
+            global_mint_amount_ctxt
+                .owner
+                .from_arcis(new_global_mint_amount),
+        )
+    }
+
+    // Lottery
+
+    /**
+     * Debits the ticket price from the buyer and burns it from the global supply
+     * Status Codes: SUCCESS, INSUFFICIENT_FUNDS
+     */
+    #[instruction]
+    pub fn buy_ticket(
+        global_mint_amount_ctxt: Enc<Mxe, u64>,
+        buyer_balance_ctxt: Enc<Shared, u64>,
+        ticket_price: u64,
+        ticket_tag: u64,
+    ) -> (u8, Enc<Mxe, u64>, Enc<Shared, u64>, Enc<Shared, u64>) {
+        let global_mint_amount = global_mint_amount_ctxt.to_arcis();
+        let buyer_balance = buyer_balance_ctxt.to_arcis();
+        let buyer_key = buyer_balance_ctxt.owner.public_key;
+
+        let status_code = if ticket_price > buyer_balance || ticket_price > global_mint_amount {
+            INSUFFICIENT_FUNDS
+        } else {
+            SUCCESS
+        };
+
+        let (new_global_mint_amount, new_buyer_balance) = if status_code == SUCCESS {
+            (
+                global_mint_amount - ticket_price,
+                buyer_balance - ticket_price,
+            )
+        } else {
+            (global_mint_amount, buyer_balance)
+        };
+
+        (
+            status_code.reveal(),
+            global_mint_amount_ctxt
+                .owner
+                .from_arcis(new_global_mint_amount),
+            buyer_balance_ctxt.owner.from_arcis(new_buyer_balance),
+            // Ownership proof under a fresh nonce, only the buyer's key decrypts it back to the tag
+            Shared::new(buyer_key).from_arcis(ticket_tag),
+        )
+    }
+
+    // Adds a freshly minted prize to the claimer and the global supply, once the claimer
+    // proved they own the winning tickets
+    fn mint_prize(
+        global_mint_amount: u64,
+        claimer_balance: u64,
+        prize: u64,
+        is_owner: bool,
+    ) -> (u8, u64, u64) {
+        let new_supply = global_mint_amount as u128 + prize as u128;
+        let new_balance = claimer_balance as u128 + prize as u128;
+        let status_code = if !is_owner {
+            NOT_OWNER
+        } else if new_supply > u64::MAX as u128 || new_balance > u64::MAX as u128 {
+            MATH_OVERFLOW
+        } else {
+            SUCCESS
+        };
+
+        if status_code == SUCCESS {
+            (
+                status_code,
+                global_mint_amount + prize,
+                claimer_balance + prize,
+            )
+        } else {
+            (status_code, global_mint_amount, claimer_balance)
+        }
+    }
+
+    /**
+     * Mints the full prize pool to the holder of every winning ticket
+     * Ticket owner ciphertexts and the claim are decrypted with the key given by the
+     * claimer. The owner proofs only match the ticket tags under the key the tickets were
+     * bought with, and only its holder can make the claim out to the signing wallet.
+     * The claim is made for the first winning ticket
+     * Status Codes: SUCCESS, NOT_OWNER, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn claim_jackpot(
+        global_mint_amount_ctxt: Enc<Mxe, u64>,
+        claimer_balance_ctxt: Enc<Shared, u64>,
+        ticket_owner_0_ctxt: Enc<Shared, u64>,
+        ticket_owner_1_ctxt: Enc<Shared, u64>,
+        ticket_owner_2_ctxt: Enc<Shared, u64>,
+        ticket_owner_3_ctxt: Enc<Shared, u64>,
+        ticket_owner_4_ctxt: Enc<Shared, u64>,
+        claim_ctxt: Enc<Shared, TicketClaim>,
+        ticket_tags: [u64; WINNING_TICKETS],
+        claimer: u128,
+        prize_pool: u64,
+    ) -> (u8, Enc<Mxe, u64>, Enc<Shared, u64>) {
+        let ticket_owners = [
+            ticket_owner_0_ctxt.to_arcis(),
+            ticket_owner_1_ctxt.to_arcis(),
+            ticket_owner_2_ctxt.to_arcis(),
+            ticket_owner_3_ctxt.to_arcis(),
+            ticket_owner_4_ctxt.to_arcis(),
+        ];
+        let claim = claim_ctxt.to_arcis();
+        let mut is_owner = claim.ticket_tag == ticket_tags[0] && claim.claimer == claimer;
+        for i in 0..WINNING_TICKETS {
+            is_owner = is_owner && ticket_owners[i] == ticket_tags[i];
+        }
+
+        let (status_code, new_global_mint_amount, new_claimer_balance) = mint_prize(
+            global_mint_amount_ctxt.to_arcis(),
+            claimer_balance_ctxt.to_arcis(),
+            prize_pool,
+            is_owner,
+        );
+
+        (
+            status_code.reveal(),
+            global_mint_amount_ctxt
+                .owner
+                .from_arcis(new_global_mint_amount),
+            claimer_balance_ctxt.owner.from_arcis(new_claimer_balance),
+        )
+    }
+
+    /**
+     * Mints the fixed ticket prize for a single winning ticket
+     * Ownership is checked like claim_jackpot
+     * Status Codes: SUCCESS, NOT_OWNER, MATH_OVERFLOW
+     */
+    #[instruction]
+    pub fn redeem_ticket(
+        global_mint_amount_ctxt: Enc<Mxe, u64>,
+        claimer_balance_ctxt: Enc<Shared, u64>,
+        ticket_owner_ctxt: Enc<Shared, u64>,
+        claim_ctxt: Enc<Shared, TicketClaim>,
+        ticket_tag: u64,
+        claimer: u128,
+        ticket_prize: u64,
+    ) -> (u8, Enc<Mxe, u64>, Enc<Shared, u64>) {
+        let claim = claim_ctxt.to_arcis();
+        let (status_code, new_global_mint_amount, new_claimer_balance) = mint_prize(
+            global_mint_amount_ctxt.to_arcis(),
+            claimer_balance_ctxt.to_arcis(),
+            ticket_prize,
+            ticket_owner_ctxt.to_arcis() == ticket_tag
+                && claim.ticket_tag == ticket_tag
+                && claim.claimer == claimer,
+        );
+
+        (
+            status_code.reveal(),
+            global_mint_amount_ctxt
+                .owner
+                .from_arcis(new_global_mint_amount),
+            claimer_balance_ctxt.owner.from_arcis(new_claimer_balance),
+        )
+    }
+
+    /**
+     * Moves a ticket's ownership proof from the key it is held under to a new one
+     * Authorized like a claim: the proof has to decrypt to the ticket tag under the
+     * claim's key, and the claim has to name the ticket and the signing wallet
+     * Status Codes: SUCCESS, NOT_OWNER
+     */
+    #[instruction]
+    pub fn rekey_ticket(
+        ticket_owner_ctxt: Enc<Shared, u64>,
+        claim_ctxt: Enc<Shared, TicketClaim>,
+        new_owner_ctxt: Enc<Shared, EmptyStruct>,
+        ticket_tag: u64,
+        claimer: u128,
+    ) -> (u8, Enc<Shared, u64>) {
+        let claim = claim_ctxt.to_arcis();
+        let status_code = if ticket_owner_ctxt.to_arcis() == ticket_tag
+            && claim.ticket_tag == ticket_tag
+            && claim.claimer == claimer
+        {
+            SUCCESS
+        } else {
+            NOT_OWNER
+        };
+
+        (
+            status_code.reveal(),
+            new_owner_ctxt.owner.from_arcis(ticket_tag),
+        )
+    }
+
+    /**
+     * Picks WINNING_TICKETS distinct ticket numbers in 0..tickets_sold, rejection
+     * sampling like the transfer variance_roll (duplicates are rejected too)
+     * Status Codes: SUCCESS, RNG_FAILURE (couldn't find every ticket in DRAW_ATTEMPTS)
+     */
+    #[instruction]
+    pub fn draw_lottery(tickets_sold: u16) -> (u8, u16, u16, u16, u16, u16) {
+        let range_size = if tickets_sold == 0 {
+            1_u128
+        } else {
+            tickets_sold as u128
+        };
+        let rejection_threshold = 65536_u128 - (65536_u128 % range_size);
+
+        let mut winners = [0_u128; WINNING_TICKETS];
+        let mut all_found = 1_u8;
+
+        for i in 0..WINNING_TICKETS {
+            let mut found_valid = 0_u8;
+            let mut result = 0_u128;
+
+            for _ in 0..DRAW_ATTEMPTS {
+                let candidate = if found_valid == 0 {
+                    ArcisRNG::gen_integer_from_width(16)
+                } else {
+                    0_u128
+                };
+                let ticket = candidate % range_size;
+
+                let mut is_duplicate = 0_u8;
+                for (j, winner) in winners.iter().enumerate() {
+                    if j < i && *winner == ticket {
+                        is_duplicate = 1_u8;
+                    }
+                }
+
+                let is_valid = if candidate < rejection_threshold && is_duplicate == 0 {
+                    1_u8
+                } else {
+                    0_u8
+                };
+
+                let should_update = if found_valid == 0 { is_valid } else { 0_u8 };
+                result = if should_update == 1 { ticket } else { result };
+                found_valid = if should_update == 1 {
+                    1_u8
+                } else {
+                    found_valid
+                };
+            }
+
+            winners[i] = result;
+            if found_valid == 0 {
+                all_found = 0_u8;
+            }
+        }
+
+        let status_code = if all_found == 1 { SUCCESS } else { RNG_FAILURE };
+
+        (
+            status_code.reveal(),
+            (winners[0] as u16).reveal(),
+            (winners[1] as u16).reveal(),
+            (winners[2] as u16).reveal(),
+            (winners[3] as u16).reveal(),
+            (winners[4] as u16).reveal(),
         )
     }
 }
@@ -268,6 +899,6 @@ mod circuits {
 //final_amt = (amt * rnd(variance)) - fee + nav_adj
 //amt = amt you want to send
 //variance = -100 to +100 % (u8 as 0-100)
-//fee = 0bps to 255bps (0 variance = 255bps, 100 variance = 0bps)
+//fee = DCConfig transfer fee curve (default 0 variance = 255bps, 255 variance = 0bps)
 //nav adj = -100% to N% based on degen_cash/usdc deposits
-//0.5% withdrawal fee
+//withdrawal fee = DCConfig.withdraw_fee_bps (default 0.5%)
@@ -0,0 +1,73 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{self, EmptyStruct, INSUFFICIENT_FUNDS, MATH_OVERFLOW, SUCCESS};
+
+fn user_key(seed: u64) -> ArcisPublicKey {
+    ArcisPublicKey::from_values(&[Number::from(seed)])
+}
+
+#[test]
+fn close_only_an_empty_balance() {
+    let empty = Shared::new(user_key(16)).from_arcis(0_u64);
+    assert!(circuits::close_user_dc_balance(empty));
+
+    let funded = Shared::new(user_key(16)).from_arcis(1_u64);
+    assert!(!circuits::close_user_dc_balance(funded));
+}
+
+#[test]
+fn rotate_keeps_balance_under_new_key() {
+    let balance = Shared::new(user_key(16)).from_arcis(1_234_u64);
+    let new_owner = Shared::new(user_key(17)).from_arcis(EmptyStruct);
+
+    let rotated = circuits::rotate_dc_key(balance, new_owner);
+
+    assert_eq!(rotated.to_arcis(), 1_234);
+}
+
+struct SubAccountTransferResult {
+    status_code: u8,
+    sender_balance: u64,
+    receiver_balance: u64,
+}
+
+fn sub_account_transfer(
+    sender_balance: u64,
+    receiver_balance: u64,
+    transfer_amount: u64,
+) -> SubAccountTransferResult {
+    let sender_balance_ctxt = Shared::new(user_key(16)).from_arcis(sender_balance);
+    let receiver_balance_ctxt = Shared::new(user_key(17)).from_arcis(receiver_balance);
+
+    let (status_code, new_sender_balance, new_receiver_balance) =
+        circuits::sub_account_transfer(sender_balance_ctxt, receiver_balance_ctxt, transfer_amount);
+
+    SubAccountTransferResult {
+        status_code,
+        sender_balance: new_sender_balance.to_arcis(),
+        receiver_balance: new_receiver_balance.to_arcis(),
+    }
+}
+
+#[test]
+fn sub_account_transfer_moves_exact_amount() {
+    let r = sub_account_transfer(1_000, 50, 1_000);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.sender_balance, 0);
+    assert_eq!(r.receiver_balance, 1_050);
+}
+
+#[test]
+fn sub_account_transfer_insufficient_funds() {
+    let r = sub_account_transfer(999, 50, 1_000);
+    assert_eq!(r.status_code, INSUFFICIENT_FUNDS);
+    assert_eq!(r.sender_balance, 999);
+    assert_eq!(r.receiver_balance, 50);
+}
+
+#[test]
+fn sub_account_transfer_overflow() {
+    let r = sub_account_transfer(1_000, u64::MAX - 1, 2);
+    assert_eq!(r.status_code, MATH_OVERFLOW);
+    assert_eq!(r.sender_balance, 1_000);
+    assert_eq!(r.receiver_balance, u64::MAX - 1);
+}
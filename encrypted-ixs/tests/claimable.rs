@@ -0,0 +1,93 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{self, INSUFFICIENT_FUNDS, MATH_OVERFLOW, SUCCESS};
+
+const GLOBAL_DC_BALANCE: u64 = 10_000_000;
+
+// Reserves match the DC supply (NAV 100%) and no variance, so only the fee moves the charge
+fn send_claimable_transfer(
+    sender_balance: u64,
+    transfer_amount: u64,
+    fee_bps: u16,
+) -> (u8, u64, u64, u64) {
+    let sender_key = ArcisPublicKey::from_values(&[Number::from(16_u64)]);
+
+    let (status_code, _, new_sender_balance, new_global_dc_balance, claim_amount) =
+        circuits::send_claimable_transfer(
+            GLOBAL_DC_BALANCE,
+            Mxe::get().from_arcis(GLOBAL_DC_BALANCE),
+            Shared::new(sender_key).from_arcis(sender_balance),
+            transfer_amount,
+            0,
+            fee_bps,
+        );
+    (
+        status_code,
+        new_sender_balance.to_arcis(),
+        new_global_dc_balance.to_arcis(),
+        claim_amount.to_arcis(),
+    )
+}
+
+fn claim_transfer(claim_amount: u64, claimer_balance: u64) -> (u8, u64) {
+    let claimer_key = ArcisPublicKey::from_values(&[Number::from(17_u64)]);
+
+    let (status_code, new_claimer_balance) = circuits::claim_transfer(
+        Mxe::get().from_arcis(claim_amount),
+        Shared::new(claimer_key).from_arcis(claimer_balance),
+    );
+    (status_code, new_claimer_balance.to_arcis())
+}
+
+#[test]
+fn send_claimable_transfer_moves_amount_into_claim() {
+    assert_eq!(
+        send_claimable_transfer(1_000, 400, 0),
+        (SUCCESS, 600, GLOBAL_DC_BALANCE, 400)
+    );
+}
+
+#[test]
+fn send_claimable_transfer_entire_balance() {
+    assert_eq!(
+        send_claimable_transfer(1_000, 1_000, 0),
+        (SUCCESS, 0, GLOBAL_DC_BALANCE, 1_000)
+    );
+}
+
+#[test]
+fn send_claimable_transfer_charges_fee() {
+    // 1% of 1_000 on top of the claim, added to the supply like a transfer fee
+    assert_eq!(
+        send_claimable_transfer(2_000, 1_000, 100),
+        (SUCCESS, 990, GLOBAL_DC_BALANCE + 10, 1_000)
+    );
+}
+
+#[test]
+fn send_claimable_transfer_fee_exceeds_balance() {
+    assert_eq!(
+        send_claimable_transfer(1_000, 1_000, 100),
+        (INSUFFICIENT_FUNDS, 1_000, GLOBAL_DC_BALANCE, 0)
+    );
+}
+
+#[test]
+fn send_claimable_transfer_insufficient_funds() {
+    assert_eq!(
+        send_claimable_transfer(999, 1_000, 0),
+        (INSUFFICIENT_FUNDS, 999, GLOBAL_DC_BALANCE, 0)
+    );
+}
+
+#[test]
+fn claim_transfer_credits_claimer() {
+    assert_eq!(claim_transfer(400, 0), (SUCCESS, 400));
+}
+
+#[test]
+fn claim_transfer_overflowing_claimer_balance() {
+    assert_eq!(
+        claim_transfer(2, u64::MAX - 1),
+        (MATH_OVERFLOW, u64::MAX - 1)
+    );
+}
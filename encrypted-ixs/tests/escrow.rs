@@ -0,0 +1,98 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{
+    self, EscrowClaim, INSUFFICIENT_FUNDS, MATH_OVERFLOW, SECRET_MISMATCH, SUCCESS,
+};
+
+const CLAIMER: u128 = 0xc1a1;
+
+fn claim_escrow_as(
+    escrow_amount: u64,
+    secret: u128,
+    claim_secret: u128,
+    claimer_balance: u64,
+    signer: u128,
+) -> (u8, u64) {
+    let creator_key = ArcisPublicKey::from_values(&[Number::from(16_u64)]);
+    let claimer_key = ArcisPublicKey::from_values(&[Number::from(17_u64)]);
+
+    let (status_code, new_claimer_balance) = circuits::claim_escrow(
+        Mxe::get().from_arcis(escrow_amount),
+        Shared::new(creator_key).from_arcis(secret),
+        Shared::new(claimer_key).from_arcis(EscrowClaim {
+            secret: claim_secret,
+            claimer: CLAIMER,
+        }),
+        Shared::new(claimer_key).from_arcis(claimer_balance),
+        signer,
+    );
+    (status_code, new_claimer_balance.to_arcis())
+}
+
+fn claim_escrow(
+    escrow_amount: u64,
+    secret: u128,
+    claim_secret: u128,
+    claimer_balance: u64,
+) -> (u8, u64) {
+    claim_escrow_as(
+        escrow_amount,
+        secret,
+        claim_secret,
+        claimer_balance,
+        CLAIMER,
+    )
+}
+
+fn expire_escrow(escrow_amount: u64, global_mint_amount: u64) -> (u8, u64) {
+    let (status_code, new_global_mint_amount) = circuits::expire_escrow(
+        Mxe::get().from_arcis(escrow_amount),
+        Mxe::get().from_arcis(global_mint_amount),
+    );
+    (status_code, new_global_mint_amount.to_arcis())
+}
+
+#[test]
+fn expire_escrow_burns_from_supply() {
+    assert_eq!(expire_escrow(1_000, 10_000), (SUCCESS, 9_000));
+}
+
+#[test]
+fn expire_escrow_burns_entire_supply() {
+    assert_eq!(expire_escrow(10_000, 10_000), (SUCCESS, 0));
+}
+
+#[test]
+fn expire_escrow_larger_than_supply() {
+    assert_eq!(expire_escrow(10_001, 10_000), (INSUFFICIENT_FUNDS, 10_000));
+}
+
+#[test]
+fn claim_escrow_with_matching_secret() {
+    assert_eq!(claim_escrow(1_000, 42, 42, 500), (SUCCESS, 1_500));
+}
+
+#[test]
+fn claim_escrow_with_wrong_secret() {
+    assert_eq!(claim_escrow(1_000, 42, 43, 500), (SECRET_MISMATCH, 500));
+}
+
+#[test]
+fn claim_escrow_copied_by_other_signer() {
+    assert_eq!(
+        claim_escrow_as(1_000, 42, 42, 500, CLAIMER + 1),
+        (SECRET_MISMATCH, 500)
+    );
+}
+
+#[test]
+fn claim_escrow_wrong_secret_checked_before_overflow() {
+    assert_eq!(
+        claim_escrow(u64::MAX, 42, 0, u64::MAX),
+        (SECRET_MISMATCH, u64::MAX)
+    );
+}
+
+#[test]
+fn claim_escrow_overflowing_claimer_balance() {
+    assert_eq!(claim_escrow(u64::MAX, 42, 42, 1), (MATH_OVERFLOW, 1));
+}
@@ -0,0 +1,240 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{
+    self, EmptyStruct, TicketClaim, INSUFFICIENT_FUNDS, MATH_OVERFLOW, NOT_OWNER, SUCCESS,
+    WINNING_TICKETS,
+};
+
+const CLAIMER: u128 = 0xc1a1;
+
+fn owner_key() -> ArcisPublicKey {
+    ArcisPublicKey::from_values(&[Number::from(16_u64)])
+}
+
+// Ownership proof as written by buy_ticket
+fn owner_proof(ticket_tag: u64) -> Enc<Shared, u64> {
+    Shared::new(owner_key()).from_arcis(ticket_tag)
+}
+
+// Fresh claim made with the owner key for CLAIMER
+fn ticket_claim(ticket_tag: u64) -> Enc<Shared, TicketClaim> {
+    Shared::new(owner_key()).from_arcis(TicketClaim {
+        ticket_tag,
+        claimer: CLAIMER,
+    })
+}
+
+fn buy_ticket(global_mint_amount: u64, buyer_balance: u64, ticket_price: u64) -> (u8, u64, u64) {
+    let (status_code, new_global_mint_amount, new_buyer_balance, _) = circuits::buy_ticket(
+        Mxe::get().from_arcis(global_mint_amount),
+        Shared::new(owner_key()).from_arcis(buyer_balance),
+        ticket_price,
+        7,
+    );
+    (
+        status_code,
+        new_global_mint_amount.to_arcis(),
+        new_buyer_balance.to_arcis(),
+    )
+}
+
+#[test]
+fn buy_ticket_encrypts_owner_proof() {
+    let (status_code, _, _, owner_proof) = circuits::buy_ticket(
+        Mxe::get().from_arcis(10_000_u64),
+        Shared::new(owner_key()).from_arcis(500_u64),
+        100,
+        7,
+    );
+    assert_eq!(status_code, SUCCESS);
+    assert_eq!(owner_proof.to_arcis(), 7);
+}
+
+#[test]
+fn buy_ticket_burns_price() {
+    assert_eq!(buy_ticket(10_000, 500, 100), (SUCCESS, 9_900, 400));
+}
+
+#[test]
+fn buy_ticket_with_exact_balance() {
+    assert_eq!(buy_ticket(10_000, 100, 100), (SUCCESS, 9_900, 0));
+}
+
+#[test]
+fn buy_ticket_without_enough_balance() {
+    assert_eq!(
+        buy_ticket(10_000, 99, 100),
+        (INSUFFICIENT_FUNDS, 10_000, 99)
+    );
+}
+
+fn draw_lottery(tickets_sold: u16) -> Vec<u16> {
+    let (status_code, w0, w1, w2, w3, w4) = circuits::draw_lottery(tickets_sold);
+    assert_eq!(status_code, SUCCESS);
+    vec![w0, w1, w2, w3, w4]
+}
+
+fn assert_distinct_in_range(winners: &[u16], tickets_sold: u16) {
+    assert_eq!(winners.len(), WINNING_TICKETS);
+    for (i, winner) in winners.iter().enumerate() {
+        assert!(*winner < tickets_sold);
+        assert!(!winners[..i].contains(winner));
+    }
+}
+
+#[test]
+fn draw_lottery_picks_distinct_tickets() {
+    for _ in 0..10 {
+        assert_distinct_in_range(&draw_lottery(500), 500);
+    }
+}
+
+#[test]
+fn draw_lottery_with_exactly_enough_tickets() {
+    let mut winners = draw_lottery(WINNING_TICKETS as u16);
+    assert_distinct_in_range(&winners, WINNING_TICKETS as u16);
+    winners.sort();
+    assert_eq!(winners, vec![0, 1, 2, 3, 4]);
+}
+
+fn redeem_ticket_as(
+    owner_proof_tag: u64,
+    claim_tag: u64,
+    signer: u128,
+    global_mint_amount: u64,
+) -> (u8, u64, u64) {
+    let (status_code, new_global_mint_amount, new_claimer_balance) = circuits::redeem_ticket(
+        Mxe::get().from_arcis(global_mint_amount),
+        Shared::new(owner_key()).from_arcis(400_u64),
+        owner_proof(owner_proof_tag),
+        ticket_claim(claim_tag),
+        7,
+        signer,
+        20,
+    );
+    (
+        status_code,
+        new_global_mint_amount.to_arcis(),
+        new_claimer_balance.to_arcis(),
+    )
+}
+
+fn redeem_ticket(global_mint_amount: u64, owner_proof_tag: u64) -> (u8, u64, u64) {
+    redeem_ticket_as(owner_proof_tag, 7, CLAIMER, global_mint_amount)
+}
+
+#[test]
+fn redeem_ticket_mints_prize() {
+    assert_eq!(redeem_ticket(10_000, 7), (SUCCESS, 10_020, 420));
+}
+
+#[test]
+fn redeem_ticket_not_owner() {
+    assert_eq!(redeem_ticket(10_000, 8), (NOT_OWNER, 10_000, 400));
+}
+
+#[test]
+fn redeem_ticket_claim_for_other_ticket() {
+    assert_eq!(
+        redeem_ticket_as(7, 8, CLAIMER, 10_000),
+        (NOT_OWNER, 10_000, 400)
+    );
+}
+
+#[test]
+fn redeem_ticket_claim_copied_by_other_signer() {
+    assert_eq!(
+        redeem_ticket_as(7, 7, CLAIMER + 1, 10_000),
+        (NOT_OWNER, 10_000, 400)
+    );
+}
+
+#[test]
+fn redeem_ticket_supply_overflow() {
+    assert_eq!(
+        redeem_ticket(u64::MAX - 10, 7),
+        (MATH_OVERFLOW, u64::MAX - 10, 400)
+    );
+}
+
+fn new_owner_key() -> ArcisPublicKey {
+    ArcisPublicKey::from_values(&[Number::from(17_u64)])
+}
+
+fn rekey_ticket(owner_proof_tag: u64, signer: u128) -> (u8, Enc<Shared, u64>) {
+    circuits::rekey_ticket(
+        owner_proof(owner_proof_tag),
+        ticket_claim(7),
+        Shared::new(new_owner_key()).from_arcis(EmptyStruct),
+        7,
+        signer,
+    )
+}
+
+#[test]
+fn rekey_ticket_moves_proof_to_new_key() {
+    let (status_code, new_owner_proof) = rekey_ticket(7, CLAIMER);
+    assert_eq!(status_code, SUCCESS);
+    assert_eq!(new_owner_proof.to_arcis(), 7);
+    assert_eq!(new_owner_proof.owner.public_key, new_owner_key());
+}
+
+#[test]
+fn rekey_ticket_not_owner() {
+    let (status_code, _) = rekey_ticket(8, CLAIMER);
+    assert_eq!(status_code, NOT_OWNER);
+}
+
+#[test]
+fn rekey_ticket_claim_copied_by_other_signer() {
+    let (status_code, _) = rekey_ticket(7, CLAIMER + 1);
+    assert_eq!(status_code, NOT_OWNER);
+}
+
+fn claim_jackpot_as(owner_proof_tags: [u64; WINNING_TICKETS], signer: u128) -> (u8, u64, u64) {
+    let (status_code, new_global_mint_amount, new_claimer_balance) = circuits::claim_jackpot(
+        Mxe::get().from_arcis(10_000_u64),
+        Shared::new(owner_key()).from_arcis(400_u64),
+        owner_proof(owner_proof_tags[0]),
+        owner_proof(owner_proof_tags[1]),
+        owner_proof(owner_proof_tags[2]),
+        owner_proof(owner_proof_tags[3]),
+        owner_proof(owner_proof_tags[4]),
+        ticket_claim(10),
+        [10, 11, 12, 13, 14],
+        signer,
+        5_000,
+    );
+    (
+        status_code,
+        new_global_mint_amount.to_arcis(),
+        new_claimer_balance.to_arcis(),
+    )
+}
+
+fn claim_jackpot(owner_proof_tags: [u64; WINNING_TICKETS]) -> (u8, u64, u64) {
+    claim_jackpot_as(owner_proof_tags, CLAIMER)
+}
+
+#[test]
+fn claim_jackpot_mints_prize_pool() {
+    assert_eq!(
+        claim_jackpot([10, 11, 12, 13, 14]),
+        (SUCCESS, 15_000, 5_400)
+    );
+}
+
+#[test]
+fn claim_jackpot_needs_every_ticket() {
+    assert_eq!(
+        claim_jackpot([10, 11, 12, 13, 99]),
+        (NOT_OWNER, 10_000, 400)
+    );
+}
+
+#[test]
+fn claim_jackpot_claim_copied_by_other_signer() {
+    assert_eq!(
+        claim_jackpot_as([10, 11, 12, 13, 14], CLAIMER + 1),
+        (NOT_OWNER, 10_000, 400)
+    );
+}
@@ -0,0 +1,46 @@
+use dc_status::DCStatus;
+use encrypted_ixs::circuits;
+
+#[test]
+fn circuit_status_codes_match_dc_status() {
+    assert_eq!(circuits::SUCCESS, DCStatus::Success.code());
+    assert_eq!(circuits::MATH_OVERFLOW, DCStatus::MathOverflow.code());
+    assert_eq!(
+        circuits::INSUFFICIENT_FUNDS,
+        DCStatus::InsufficientFunds.code()
+    );
+    assert_eq!(circuits::RNG_FAILURE, DCStatus::RNGFailure.code());
+    assert_eq!(circuits::SECRET_MISMATCH, DCStatus::SecretMismatch.code());
+    assert_eq!(circuits::NOT_OWNER, DCStatus::NotOwner.code());
+    assert_eq!(
+        circuits::MAX_AMOUNT_EXCEEDED,
+        DCStatus::MaxAmountExceeded.code()
+    );
+}
+
+#[test]
+fn circuit_status_codes_decode() {
+    for code in [
+        circuits::SUCCESS,
+        circuits::MATH_OVERFLOW,
+        circuits::INSUFFICIENT_FUNDS,
+        circuits::RNG_FAILURE,
+        circuits::SECRET_MISMATCH,
+        circuits::NOT_OWNER,
+        circuits::MAX_AMOUNT_EXCEEDED,
+    ] {
+        assert_eq!(DCStatus::from_code(code).code(), code);
+    }
+    assert_eq!(
+        DCStatus::from_code(DCStatus::Unknown.code()),
+        DCStatus::Unknown
+    );
+    assert_eq!(DCStatus::from_code(8), DCStatus::Unknown);
+    assert_eq!(DCStatus::from_code(u8::MAX), DCStatus::Unknown);
+}
+
+#[test]
+fn unknown_status_keeps_its_index() {
+    // Borsh serializes the variant index, Unknown is pinned to 7 so it never shifts
+    assert_eq!(DCStatus::Unknown.code(), 7);
+}
@@ -0,0 +1,151 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{
+    self, INSUFFICIENT_FUNDS, MATH_OVERFLOW, MAX_AMOUNT_EXCEEDED, SUCCESS,
+};
+
+const MAX_TRANSFER_AMOUNT: u64 = 1_000_000_000;
+
+fn user_key(seed: u64) -> ArcisPublicKey {
+    ArcisPublicKey::from_values(&[Number::from(seed)])
+}
+
+#[derive(Debug, PartialEq)]
+struct TransferResult {
+    status_code: u8,
+    sender_balance: u64,
+    global_dc_balance: u64,
+    receiver_balance: u64,
+}
+
+// Reserves match the DC supply (NAV 100%) and no variance, so only the fee moves the charge
+fn confidential_transfer(
+    sender_balance: u64,
+    receiver_balance: u64,
+    transfer_amount: u64,
+    fee_bps: u16,
+) -> TransferResult {
+    let global_dc_balance = 10_000_000;
+
+    let (status_code, sender_balance, global_dc_balance, receiver_balance) =
+        circuits::confidential_transfer(
+            global_dc_balance,
+            Mxe::get().from_arcis(global_dc_balance),
+            Shared::new(user_key(16)).from_arcis(sender_balance),
+            Shared::new(user_key(17)).from_arcis(receiver_balance),
+            Shared::new(user_key(16)).from_arcis(transfer_amount),
+            MAX_TRANSFER_AMOUNT,
+            0,
+            fee_bps,
+        );
+    TransferResult {
+        status_code,
+        sender_balance: sender_balance.to_arcis(),
+        global_dc_balance: global_dc_balance.to_arcis(),
+        receiver_balance: receiver_balance.to_arcis(),
+    }
+}
+
+#[test]
+fn confidential_transfer_at_par() {
+    assert_eq!(
+        confidential_transfer(1_000, 0, 400, 0),
+        TransferResult {
+            status_code: SUCCESS,
+            sender_balance: 600,
+            global_dc_balance: 10_000_000,
+            receiver_balance: 400,
+        }
+    );
+}
+
+#[test]
+fn confidential_transfer_charges_fee_to_sender() {
+    let result = confidential_transfer(1_000, 0, 400, 100);
+
+    assert_eq!(result.status_code, SUCCESS);
+    assert_eq!(result.sender_balance, 596);
+    assert_eq!(result.receiver_balance, 400);
+}
+
+#[test]
+fn confidential_transfer_up_to_max_amount() {
+    let result = confidential_transfer(MAX_TRANSFER_AMOUNT, 0, MAX_TRANSFER_AMOUNT, 0);
+
+    assert_eq!(result.status_code, SUCCESS);
+    assert_eq!(result.sender_balance, 0);
+    assert_eq!(result.receiver_balance, MAX_TRANSFER_AMOUNT);
+}
+
+#[test]
+fn confidential_transfer_above_max_amount() {
+    let sender_balance = MAX_TRANSFER_AMOUNT * 2;
+
+    assert_eq!(
+        confidential_transfer(sender_balance, 0, MAX_TRANSFER_AMOUNT + 1, 0),
+        TransferResult {
+            status_code: MAX_AMOUNT_EXCEEDED,
+            sender_balance,
+            global_dc_balance: 10_000_000,
+            receiver_balance: 0,
+        }
+    );
+}
+
+#[test]
+fn confidential_transfer_worst_case_above_balance() {
+    // The fee pushes the worst-case charge past the balance even though the amount fits
+    assert_eq!(
+        confidential_transfer(1_000, 0, 1_000, 100),
+        TransferResult {
+            status_code: INSUFFICIENT_FUNDS,
+            sender_balance: 1_000,
+            global_dc_balance: 10_000_000,
+            receiver_balance: 0,
+        }
+    );
+}
+
+#[test]
+fn confidential_transfer_overflowing_receiver_balance() {
+    let result = confidential_transfer(1_000, u64::MAX - 1, 2, 0);
+
+    assert_eq!(result.status_code, MATH_OVERFLOW);
+    assert_eq!(result.sender_balance, 1_000);
+    assert_eq!(result.receiver_balance, u64::MAX - 1);
+}
+
+#[test]
+fn transfer_overflowing_receiver_balance() {
+    // The sum is taken in u128, so the overflow check has to compare against u64::MAX
+    let (status_code, _, _, sender_balance, _, receiver_balance) = circuits::transfer(
+        10_000_000,
+        Mxe::get().from_arcis(10_000_000_u64),
+        Shared::new(user_key(16)).from_arcis(1_000_u64),
+        Shared::new(user_key(17)).from_arcis(u64::MAX - 1),
+        2,
+        0,
+        0,
+    );
+
+    assert_eq!(status_code, MATH_OVERFLOW);
+    assert_eq!(sender_balance.to_arcis(), 1_000);
+    assert_eq!(receiver_balance.to_arcis(), u64::MAX - 1);
+}
+
+#[test]
+fn transfer_reveals_amount_and_matches_confidential_transfer() {
+    let (status_code, variance, transfer_amount, sender_balance, _, receiver) = circuits::transfer(
+        10_000_000,
+        Mxe::get().from_arcis(10_000_000_u64),
+        Shared::new(user_key(16)).from_arcis(1_000_u64),
+        Shared::new(user_key(17)).from_arcis(0_u64),
+        400,
+        0,
+        100,
+    );
+
+    assert_eq!((status_code, variance, transfer_amount), (SUCCESS, 0, 400));
+    let confidential = confidential_transfer(1_000, 0, 400, 100);
+    assert_eq!(sender_balance.to_arcis(), confidential.sender_balance);
+    assert_eq!(receiver.to_arcis(), confidential.receiver_balance);
+}
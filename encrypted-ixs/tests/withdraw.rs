@@ -0,0 +1,139 @@
+use arcis_imports::*;
+use encrypted_ixs::circuits::{self, INSUFFICIENT_FUNDS, MATH_OVERFLOW, SUCCESS};
+
+struct WithdrawResult {
+    status_code: u8,
+    withdraw_amount: u64,
+    global_mint_amount: u64,
+    user_dc_balance: u64,
+}
+
+fn withdraw(
+    global_mint_amount: u64,
+    user_dc_balance: u64,
+    withdraw_amount: u64,
+    withdraw_fee_bps: u16,
+) -> WithdrawResult {
+    let global_mint_amount_ctxt = Mxe::get().from_arcis(global_mint_amount);
+    let user_key = ArcisPublicKey::from_values(&[Number::from(16_u64)]);
+    let user_dc_balance_ctxt = Shared::new(user_key).from_arcis(user_dc_balance);
+
+    let (status_code, withdraw_amount, new_global_mint_amount, new_user_dc_balance) =
+        circuits::withdraw(
+            global_mint_amount_ctxt,
+            user_dc_balance_ctxt,
+            withdraw_amount,
+            withdraw_fee_bps,
+        );
+
+    WithdrawResult {
+        status_code,
+        withdraw_amount,
+        global_mint_amount: new_global_mint_amount.to_arcis(),
+        user_dc_balance: new_user_dc_balance.to_arcis(),
+    }
+}
+
+#[test]
+fn withdraw_charges_amount_plus_fee() {
+    // 1_000 at 50 bps = 5 fee
+    let r = withdraw(10_000, 2_000, 1_000, 50);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.withdraw_amount, 1_000);
+    assert_eq!(r.global_mint_amount, 8_995);
+    assert_eq!(r.user_dc_balance, 995);
+}
+
+#[test]
+fn withdraw_fee_rounds_down() {
+    // 199 at 50 bps = 0.995 fee
+    let r = withdraw(1_000, 1_000, 199, 50);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.user_dc_balance, 801);
+}
+
+#[test]
+fn withdraw_entire_balance() {
+    let r = withdraw(1_005, 1_005, 1_000, 50);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.global_mint_amount, 0);
+    assert_eq!(r.user_dc_balance, 0);
+}
+
+#[test]
+fn withdraw_zero() {
+    let r = withdraw(1_000, 500, 0, 50);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.global_mint_amount, 1_000);
+    assert_eq!(r.user_dc_balance, 500);
+}
+
+#[test]
+fn withdraw_fee_pushes_charge_over_balance() {
+    // Amount alone fits, amount + fee doesn't
+    let r = withdraw(10_000, 1_000, 1_000, 50);
+    assert_eq!(r.status_code, INSUFFICIENT_FUNDS);
+    assert_eq!(r.global_mint_amount, 10_000);
+    assert_eq!(r.user_dc_balance, 1_000);
+}
+
+#[test]
+fn withdraw_more_than_user_balance() {
+    let r = withdraw(10_000, 100, 1_000, 0);
+    assert_eq!(r.status_code, INSUFFICIENT_FUNDS);
+    assert_eq!(r.global_mint_amount, 10_000);
+    assert_eq!(r.user_dc_balance, 100);
+}
+
+#[test]
+fn withdraw_more_than_global_supply() {
+    let r = withdraw(100, 10_000, 1_000, 0);
+    assert_eq!(r.status_code, INSUFFICIENT_FUNDS);
+    assert_eq!(r.global_mint_amount, 100);
+    assert_eq!(r.user_dc_balance, 10_000);
+}
+
+#[test]
+fn withdraw_from_empty_balance() {
+    let r = withdraw(0, 0, 1, 0);
+    assert_eq!(r.status_code, INSUFFICIENT_FUNDS);
+    assert_eq!(r.global_mint_amount, 0);
+    assert_eq!(r.user_dc_balance, 0);
+}
+
+#[test]
+fn withdraw_charge_overflowing_u64() {
+    let r = withdraw(u64::MAX, u64::MAX, u64::MAX, 50);
+    assert_eq!(r.status_code, MATH_OVERFLOW);
+    assert_eq!(r.global_mint_amount, u64::MAX);
+    assert_eq!(r.user_dc_balance, u64::MAX);
+}
+
+#[test]
+fn withdraw_max_fee_bps() {
+    // set_withdraw_fee caps the fee at 10000 bps, a 100% fee
+    let r = withdraw(u64::MAX, 1_000, 100, 10_000);
+    assert_eq!(r.status_code, SUCCESS);
+    assert_eq!(r.user_dc_balance, 1_000 - 100 - 100);
+}
+
+#[test]
+fn failed_withdraw_re_encrypts_unchanged_balance() {
+    let user_key = ArcisPublicKey::from_values(&[Number::from(16_u64)]);
+    let user_dc_balance_ctxt = Shared::new(user_key).from_arcis(100_u64);
+    let mut input_ciphertext = Vec::new();
+    user_dc_balance_ctxt.handle_outputs(&mut input_ciphertext);
+
+    let (status_code, _, _, new_user_dc_balance) = circuits::withdraw(
+        Mxe::get().from_arcis(10_000_u64),
+        user_dc_balance_ctxt,
+        1_000,
+        0,
+    );
+    let mut output_ciphertext = Vec::new();
+    new_user_dc_balance.handle_outputs(&mut output_ciphertext);
+
+    assert_eq!(status_code, INSUFFICIENT_FUNDS);
+    assert_ne!(input_ciphertext, output_ciphertext);
+    assert_eq!(new_user_dc_balance.to_arcis(), 100);
+}
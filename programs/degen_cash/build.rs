@@ -0,0 +1,112 @@
+// Pins the off-chain circuits to the artifacts produced by `arcium build`.
+//
+// circuits.sha256 is committed and lists the SHA-256 of every `build/*.arcis`
+// artifact. It generates the `<CIRCUIT>_CIRCUIT_HASH` consts that are passed to
+// `init_comp_def`, so each computation definition records the artifact it was
+// registered for.
+//
+// Every SBF build (the deployable program) fails if an artifact is missing, is unpinned
+// (all-zero hash) or no longer matches its committed hash. The artifacts aren't committed,
+// so host builds (tests, clippy, IDL) take the hashes from the manifest as is and only
+// warn about unpinned ones, unless the `verify-circuits` feature is enabled.
+//
+// The manifest can hold several artifact sets, DC_CIRCUIT_SUFFIX (default `_testnet`)
+// picks the one matching DCCircuitConfig.artifact_suffix for this deployment. It is
+// exported as CIRCUIT_SUFFIX so the program can refuse any other suffix.
+//
+// After changing a circuit in encrypted-ixs run `scripts/update-circuit-hashes.sh`.
+
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::{env, fs};
+
+const MANIFEST: &str = "circuits.sha256";
+const ARTIFACTS_DIR: &str = "../../build";
+const DEFAULT_CIRCUIT_SUFFIX: &str = "_testnet";
+const UNPINNED: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let manifest_path = crate_dir.join(MANIFEST);
+    let artifacts_dir = crate_dir.join(ARTIFACTS_DIR);
+    let verify = env::var_os("CARGO_FEATURE_VERIFY_CIRCUITS").is_some()
+        || env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "solana");
+    let suffix =
+        env::var("DC_CIRCUIT_SUFFIX").unwrap_or_else(|_| DEFAULT_CIRCUIT_SUFFIX.to_string());
+    let artifact_ext = format!("{suffix}.arcis");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+    println!("cargo:rerun-if-env-changed=DC_CIRCUIT_SUFFIX");
+
+    let manifest = fs::read_to_string(&manifest_path)
+        .unwrap_or_else(|e| panic!("Could not read {}: {}", manifest_path.display(), e));
+
+    let mut generated = format!("pub const CIRCUIT_SUFFIX: &str = {suffix:?};\n");
+    let mut mismatched = Vec::new();
+
+    for line in manifest.lines().filter(|l| !l.trim().is_empty()) {
+        let mut parts = line.split_whitespace();
+        let (committed, file) = match (parts.next(), parts.next()) {
+            (Some(hash), Some(file)) => (hash, file.trim_start_matches('*')),
+            _ => panic!("Malformed line in {MANIFEST}: {line}"),
+        };
+        // Artifact set of another deployment
+        let Some(circuit) = file.strip_suffix(&artifact_ext) else {
+            continue;
+        };
+        let hash =
+            parse_hash(committed).unwrap_or_else(|| panic!("Malformed hash in {MANIFEST}: {line}"));
+
+        if verify {
+            let artifact_path = artifacts_dir.join(file);
+            println!("cargo:rerun-if-changed={}", artifact_path.display());
+            let artifact = fs::read(&artifact_path).unwrap_or_else(|e| {
+                panic!(
+                    "Could not read circuit artifact {}: {} (run `arcium build`)",
+                    artifact_path.display(),
+                    e
+                )
+            });
+
+            let actual: String = Sha256::digest(&artifact)
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            if committed == UNPINNED || actual != committed {
+                mismatched.push(format!("  {file}: committed {committed} built {actual}"));
+            }
+        } else if committed == UNPINNED {
+            println!("cargo:warning={file} is not pinned in {MANIFEST}, its comp def is registered without a hash");
+        }
+
+        writeln!(
+            generated,
+            "pub const {}_CIRCUIT_HASH: [u8; 32] = {:?};",
+            circuit.to_uppercase(),
+            hash
+        )
+        .unwrap();
+    }
+
+    if !mismatched.is_empty() {
+        panic!(
+            "Circuit artifacts do not match {}:\n{}\nRun scripts/update-circuit-hashes.sh and commit the result.",
+            MANIFEST,
+            mismatched.join("\n")
+        );
+    }
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("circuit_hashes.rs");
+    fs::write(out_path, generated).unwrap();
+}
+
+fn parse_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(hash)
+}
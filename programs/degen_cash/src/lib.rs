@@ -38,6 +38,71 @@ pub mod degen_cash {
         Ok(())
     }
 
+    pub fn set_withdraw_fee(ctx: Context<UpdateDCConfig>, withdraw_fee_bps: u16) -> Result<()> {
+        base::set_withdraw_fee(ctx, withdraw_fee_bps)?;
+        Ok(())
+    }
+
+    pub fn set_transfer_fee_curve(
+        ctx: Context<UpdateDCConfig>,
+        transfer_max_fee_bps: u16,
+        transfer_min_fee_bps: u16,
+    ) -> Result<()> {
+        base::set_transfer_fee_curve(ctx, transfer_max_fee_bps, transfer_min_fee_bps)?;
+        Ok(())
+    }
+
+    pub fn set_max_transfer_amount(
+        ctx: Context<UpdateDCConfig>,
+        max_transfer_amount: u64,
+    ) -> Result<()> {
+        base::set_max_transfer_amount(ctx, max_transfer_amount)?;
+        Ok(())
+    }
+
+    pub fn set_min_deposit_amount(
+        ctx: Context<UpdateDCConfig>,
+        min_deposit_amount: u64,
+    ) -> Result<()> {
+        base::set_min_deposit_amount(ctx, min_deposit_amount)?;
+        Ok(())
+    }
+
+    pub fn set_pause_authority(
+        ctx: Context<UpdateDCConfig>,
+        pause_authority: Pubkey,
+    ) -> Result<()> {
+        base::set_pause_authority(ctx, pause_authority)?;
+        Ok(())
+    }
+
+    pub fn set_paused(
+        ctx: Context<PauseDC>,
+        operation: PausableOperation,
+        paused: bool,
+    ) -> Result<()> {
+        base::set_paused(ctx, operation, paused)?;
+        Ok(())
+    }
+
+    pub fn init_circuit_config(
+        ctx: Context<InitCircuitConfig>,
+        base_url: String,
+        artifact_suffix: String,
+    ) -> Result<()> {
+        base::init_circuit_config(ctx, base_url, artifact_suffix)?;
+        Ok(())
+    }
+
+    pub fn set_circuit_source(
+        ctx: Context<UpdateCircuitConfig>,
+        base_url: String,
+        artifact_suffix: String,
+    ) -> Result<()> {
+        base::set_circuit_source(ctx, base_url, artifact_suffix)?;
+        Ok(())
+    }
+
     pub fn init_create_dc_token_account_comp_def(
         ctx: Context<InitCreateDCTokenAccountCompDef>,
     ) -> Result<()> {
@@ -50,8 +115,9 @@ pub mod degen_cash {
         computation_offset: u64,
         owner_x25519: [u8; 32],
         nonce: u128,
+        index: u16,
     ) -> Result<()> {
-        base::queue_create_dc_token_account(ctx, computation_offset, owner_x25519, nonce)?;
+        base::queue_create_dc_token_account(ctx, computation_offset, owner_x25519, nonce, index)?;
         Ok(())
     }
 
@@ -64,6 +130,54 @@ pub mod degen_cash {
         Ok(())
     }
 
+    pub fn init_close_dc_token_account_comp_def(
+        ctx: Context<InitCloseDCTokenAccountCompDef>,
+    ) -> Result<()> {
+        base::init_close_dc_token_account_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_close_dc_token_account(
+        ctx: Context<QueueCloseUserDcBalance>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        base::queue_close_dc_token_account(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "close_user_dc_balance")]
+    pub fn close_user_dc_balance_callback(
+        ctx: Context<CloseUserDcBalanceCallback>,
+        output: ComputationOutputs<CloseUserDcBalanceOutput>,
+    ) -> Result<()> {
+        base::close_user_dc_balance_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_rotate_dc_key_comp_def(ctx: Context<InitRotateDCKeyCompDef>) -> Result<()> {
+        base::init_rotate_dc_key_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_rotate_dc_key(
+        ctx: Context<QueueRotateDcKey>,
+        computation_offset: u64,
+        new_owner_x25519: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        base::queue_rotate_dc_key(ctx, computation_offset, new_owner_x25519, nonce)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "rotate_dc_key")]
+    pub fn rotate_dc_key_callback(
+        ctx: Context<RotateDcKeyCallback>,
+        output: ComputationOutputs<RotateDcKeyOutput>,
+    ) -> Result<()> {
+        base::rotate_dc_key_callback(ctx, output)?;
+        Ok(())
+    }
+
     pub fn init_deposit_comp_def(ctx: Context<InitDepositCompDef>) -> Result<()> {
         base::init_deposit_comp_def(ctx)?;
         Ok(())
@@ -118,6 +232,136 @@ pub mod degen_cash {
         Ok(())
     }
 
+    pub fn init_confidential_transfer_comp_def(
+        ctx: Context<InitConfidentialTransferCompDef>,
+    ) -> Result<()> {
+        base::init_confidential_transfer_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_confidential_transfer(
+        ctx: Context<QueueConfidentialTransfer>,
+        computation_offset: u64,
+        transfer_amount: [u8; 32],
+        transfer_amount_nonce: u128,
+        max_variance: u8,
+        _reciever_pubkey: Pubkey,
+    ) -> Result<()> {
+        base::queue_confidential_transfer(
+            ctx,
+            computation_offset,
+            transfer_amount,
+            transfer_amount_nonce,
+            max_variance,
+            _reciever_pubkey,
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "confidential_transfer")]
+    pub fn confidential_transfer_callback(
+        ctx: Context<ConfidentialTransferCallback>,
+        output: ComputationOutputs<ConfidentialTransferOutput>,
+    ) -> Result<()> {
+        base::confidential_transfer_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_sub_account_transfer_comp_def(
+        ctx: Context<InitSubAccountTransferCompDef>,
+    ) -> Result<()> {
+        base::init_sub_account_transfer_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_sub_account_transfer(
+        ctx: Context<QueueSubAccountTransfer>,
+        computation_offset: u64,
+        transfer_amount: u64,
+    ) -> Result<()> {
+        base::queue_sub_account_transfer(ctx, computation_offset, transfer_amount)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "sub_account_transfer")]
+    pub fn sub_account_transfer_callback(
+        ctx: Context<SubAccountTransferCallback>,
+        output: ComputationOutputs<SubAccountTransferOutput>,
+    ) -> Result<()> {
+        base::sub_account_transfer_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_send_claimable_transfer_comp_def(
+        ctx: Context<InitSendClaimableTransferCompDef>,
+    ) -> Result<()> {
+        base::init_send_claimable_transfer_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_send_claimable_transfer(
+        ctx: Context<QueueSendClaimableTransfer>,
+        computation_offset: u64,
+        transfer_amount: u64,
+        max_variance: u8,
+        recipient: Pubkey,
+        claim_window: i64,
+    ) -> Result<()> {
+        base::queue_send_claimable_transfer(
+            ctx,
+            computation_offset,
+            transfer_amount,
+            max_variance,
+            recipient,
+            claim_window,
+        )?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "send_claimable_transfer")]
+    pub fn send_claimable_transfer_callback(
+        ctx: Context<SendClaimableTransferCallback>,
+        output: ComputationOutputs<SendClaimableTransferOutput>,
+    ) -> Result<()> {
+        base::send_claimable_transfer_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_claim_transfer_comp_def(ctx: Context<InitClaimTransferCompDef>) -> Result<()> {
+        base::init_claim_transfer_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_claim_transfer(
+        ctx: Context<QueueClaimTransfer>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        base::queue_claim_transfer(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    pub fn queue_reclaim_transfer(
+        ctx: Context<QueueReclaimTransfer>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        base::queue_reclaim_transfer(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "claim_transfer")]
+    pub fn claim_transfer_callback(
+        ctx: Context<ClaimTransferCallback>,
+        output: ComputationOutputs<ClaimTransferOutput>,
+    ) -> Result<()> {
+        base::claim_transfer_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn close_unfunded_claim(ctx: Context<CloseUnfundedClaim>) -> Result<()> {
+        base::close_unfunded_claim(ctx)?;
+        Ok(())
+    }
+
     pub fn init_withdraw_comp_def(ctx: Context<InitWithdrawCompDef>) -> Result<()> {
         base::init_withdraw_comp_def(ctx)?;
         Ok(())
@@ -141,7 +385,290 @@ pub mod degen_cash {
         Ok(())
     }
 
+    pub fn close_operation_receipt(ctx: Context<CloseOperationReceipt>) -> Result<()> {
+        base::close_operation_receipt(ctx)?;
+        Ok(())
+    }
+
     // Escrow
+    pub fn init_create_escrow_comp_def(ctx: Context<InitCreateEscrowCompDef>) -> Result<()> {
+        escrow::init_create_escrow_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_create_escrow(
+        ctx: Context<QueueCreateEscrow>,
+        computation_offset: u64,
+        escrow_id: u64,
+        amount: u64,
+        terms: EscrowTerms,
+    ) -> Result<()> {
+        escrow::queue_create_escrow(ctx, computation_offset, escrow_id, amount, terms)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "create_escrow")]
+    pub fn create_escrow_callback(
+        ctx: Context<CreateEscrowCallback>,
+        output: ComputationOutputs<CreateEscrowOutput>,
+    ) -> Result<()> {
+        escrow::create_escrow_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_release_escrow_comp_def(ctx: Context<InitReleaseEscrowCompDef>) -> Result<()> {
+        escrow::init_release_escrow_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_release_escrow(
+        ctx: Context<QueueReleaseEscrow>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        escrow::queue_release_escrow(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "release_escrow")]
+    pub fn release_escrow_callback(
+        ctx: Context<ReleaseEscrowCallback>,
+        output: ComputationOutputs<ReleaseEscrowOutput>,
+    ) -> Result<()> {
+        escrow::release_escrow_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_claim_escrow_comp_def(ctx: Context<InitClaimEscrowCompDef>) -> Result<()> {
+        escrow::init_claim_escrow_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_claim_escrow(
+        ctx: Context<QueueClaimEscrow>,
+        computation_offset: u64,
+        claim: EscrowClaim,
+    ) -> Result<()> {
+        escrow::queue_claim_escrow(ctx, computation_offset, claim)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "claim_escrow")]
+    pub fn claim_escrow_callback(
+        ctx: Context<ClaimEscrowCallback>,
+        output: ComputationOutputs<ClaimEscrowOutput>,
+    ) -> Result<()> {
+        escrow::claim_escrow_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_expire_escrow_comp_def(ctx: Context<InitExpireEscrowCompDef>) -> Result<()> {
+        escrow::init_expire_escrow_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_expire_escrow(
+        ctx: Context<QueueExpireEscrow>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        escrow::queue_expire_escrow(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "expire_escrow")]
+    pub fn expire_escrow_callback(
+        ctx: Context<ExpireEscrowCallback>,
+        output: ComputationOutputs<ExpireEscrowOutput>,
+    ) -> Result<()> {
+        escrow::expire_escrow_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn queue_refund_escrow(
+        ctx: Context<QueueRefundEscrow>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        escrow::queue_refund_escrow(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    pub fn close_unfunded_escrow(ctx: Context<CloseUnfundedEscrow>) -> Result<()> {
+        escrow::close_unfunded_escrow(ctx)?;
+        Ok(())
+    }
+
+    pub fn approve_escrow(ctx: Context<ApproveEscrow>) -> Result<()> {
+        escrow::approve_escrow(ctx)?;
+        Ok(())
+    }
+
+    pub fn heartbeat(ctx: Context<HeartbeatEscrow>) -> Result<()> {
+        escrow::heartbeat(ctx)?;
+        Ok(())
+    }
 
     // Lottery
+    pub fn create_lottery(
+        ctx: Context<CreateLottery>,
+        lottery_id: u64,
+        ticket_price: u64,
+        ticket_count: u16,
+        prize_pool: u64,
+        ticket_prize: u64,
+        claim_window: i64,
+    ) -> Result<()> {
+        lotto::create_lottery(
+            ctx,
+            lottery_id,
+            ticket_price,
+            ticket_count,
+            prize_pool,
+            ticket_prize,
+            claim_window,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_buy_ticket_comp_def(ctx: Context<InitBuyTicketCompDef>) -> Result<()> {
+        lotto::init_buy_ticket_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_buy_ticket(
+        ctx: Context<QueueBuyTicket>,
+        computation_offset: u64,
+        bearer: bool,
+    ) -> Result<()> {
+        lotto::queue_buy_ticket(ctx, computation_offset, bearer)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "buy_ticket")]
+    pub fn buy_ticket_callback(
+        ctx: Context<BuyTicketCallback>,
+        output: ComputationOutputs<BuyTicketOutput>,
+    ) -> Result<()> {
+        lotto::buy_ticket_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_draw_lottery_comp_def(ctx: Context<InitDrawLotteryCompDef>) -> Result<()> {
+        lotto::init_draw_lottery_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_draw_lottery(
+        ctx: Context<QueueDrawLottery>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        lotto::queue_draw_lottery(ctx, computation_offset)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "draw_lottery")]
+    pub fn draw_lottery_callback(
+        ctx: Context<DrawLotteryCallback>,
+        output: ComputationOutputs<DrawLotteryOutput>,
+    ) -> Result<()> {
+        lotto::draw_lottery_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_redeem_ticket_comp_def(ctx: Context<InitRedeemTicketCompDef>) -> Result<()> {
+        lotto::init_redeem_ticket_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_redeem_ticket(
+        ctx: Context<QueueRedeemTicket>,
+        computation_offset: u64,
+        claim: TicketClaim,
+    ) -> Result<()> {
+        lotto::queue_redeem_ticket(ctx, computation_offset, claim)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "redeem_ticket")]
+    pub fn redeem_ticket_callback(
+        ctx: Context<RedeemTicketCallback>,
+        output: ComputationOutputs<RedeemTicketOutput>,
+    ) -> Result<()> {
+        lotto::redeem_ticket_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_rekey_ticket_comp_def(ctx: Context<InitRekeyTicketCompDef>) -> Result<()> {
+        lotto::init_rekey_ticket_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_rekey_ticket(
+        ctx: Context<QueueRekeyTicket>,
+        computation_offset: u64,
+        claim: TicketClaim,
+        nonce: u128,
+    ) -> Result<()> {
+        lotto::queue_rekey_ticket(ctx, computation_offset, claim, nonce)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "rekey_ticket")]
+    pub fn rekey_ticket_callback(
+        ctx: Context<RekeyTicketCallback>,
+        output: ComputationOutputs<RekeyTicketOutput>,
+    ) -> Result<()> {
+        lotto::rekey_ticket_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn init_claim_jackpot_comp_def(ctx: Context<InitClaimJackpotCompDef>) -> Result<()> {
+        lotto::init_claim_jackpot_comp_def(ctx)?;
+        Ok(())
+    }
+
+    pub fn queue_claim_jackpot(
+        ctx: Context<QueueClaimJackpot>,
+        computation_offset: u64,
+        claim: TicketClaim,
+    ) -> Result<()> {
+        lotto::queue_claim_jackpot(ctx, computation_offset, claim)?;
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "claim_jackpot")]
+    pub fn claim_jackpot_callback(
+        ctx: Context<ClaimJackpotCallback>,
+        output: ComputationOutputs<ClaimJackpotOutput>,
+    ) -> Result<()> {
+        lotto::claim_jackpot_callback(ctx, output)?;
+        Ok(())
+    }
+
+    pub fn create_lottery_schedule(
+        ctx: Context<CreateLotterySchedule>,
+        ticket_price: u64,
+        ticket_count: u16,
+        prize_pool: u64,
+        ticket_prize: u64,
+        claim_window: i64,
+        round_duration: i64,
+    ) -> Result<()> {
+        lotto::create_lottery_schedule(
+            ctx,
+            ticket_price,
+            ticket_count,
+            prize_pool,
+            ticket_prize,
+            claim_window,
+            round_duration,
+        )?;
+        Ok(())
+    }
+
+    pub fn queue_advance_lottery_round(
+        ctx: Context<QueueAdvanceLotteryRound>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        lotto::queue_advance_lottery_round(ctx, computation_offset)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,114 @@
+// Per deployment circuit source for the comp defs
+// DCCircuitConfig must be created before any init_*_comp_def is called
+//
+// Only the program upgrade authority can create it, otherwise whoever calls first after
+// deployment would pick the URL every comp def loads its circuit from.
+//
+// Arcium 0.3 has no instruction to change the source of an initialized comp def
+// (init_computation_definition is the only one taking a CircuitSource), so
+// set_circuit_source can only move the host for comp defs initialized afterwards.
+// Existing comp defs keep their URL, serve the pinned artifacts there as well.
+//
+// The artifact suffix is fixed at build time: the comp def hashes come from the
+// DC_CIRCUIT_SUFFIX artifact set (see build.rs), any other suffix would register
+// URLs for artifacts the hashes were not computed from.
+
+use crate::base::ErrorCode;
+use crate::{
+    DCCircuitConfig, CIRCUIT_SUFFIX, DC_CIRCUIT_CONFIG_SEED, MAX_ARTIFACT_SUFFIX_LEN,
+    MAX_CIRCUITS_URL_LEN,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+pub fn init_circuit_config(
+    ctx: Context<InitCircuitConfig>,
+    base_url: String,
+    artifact_suffix: String,
+) -> Result<()> {
+    validate_circuit_source(&base_url, &artifact_suffix)?;
+
+    ctx.accounts
+        .circuit_config_account
+        .set_inner(DCCircuitConfig {
+            authority: ctx.accounts.payer.key(),
+            base_url,
+            artifact_suffix,
+        });
+    emit_circuit_source_updated(&ctx.accounts.circuit_config_account);
+    Ok(())
+}
+
+pub fn set_circuit_source(
+    ctx: Context<UpdateCircuitConfig>,
+    base_url: String,
+    artifact_suffix: String,
+) -> Result<()> {
+    validate_circuit_source(&base_url, &artifact_suffix)?;
+
+    ctx.accounts.circuit_config_account.base_url = base_url;
+    ctx.accounts.circuit_config_account.artifact_suffix = artifact_suffix;
+    emit_circuit_source_updated(&ctx.accounts.circuit_config_account);
+    Ok(())
+}
+
+fn validate_circuit_source(base_url: &str, artifact_suffix: &str) -> Result<()> {
+    if base_url.is_empty()
+        || base_url.len() > MAX_CIRCUITS_URL_LEN
+        || artifact_suffix.len() > MAX_ARTIFACT_SUFFIX_LEN
+    {
+        return Err(ErrorCode::InvalidConfig.into());
+    }
+    if artifact_suffix != CIRCUIT_SUFFIX {
+        return Err(ErrorCode::ArtifactSuffixMismatch.into());
+    }
+    Ok(())
+}
+
+fn emit_circuit_source_updated(config: &DCCircuitConfig) {
+    emit!(CircuitSourceUpdatedEvent {
+        base_url: config.base_url.clone(),
+        artifact_suffix: config.artifact_suffix.clone(),
+    });
+}
+
+#[derive(Accounts)]
+pub struct InitCircuitConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(payer.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCCircuitConfig::INIT_SPACE,
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Account<'info, DCCircuitConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCircuitConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+        has_one = authority,
+    )]
+    pub circuit_config_account: Account<'info, DCCircuitConfig>,
+}
+
+#[event]
+pub struct CircuitSourceUpdatedEvent {
+    pub base_url: String,
+    pub artifact_suffix: String,
+}
@@ -0,0 +1,304 @@
+// Claim DC sent to this wallet with send_claimable_transfer
+// The recipient needs a DC account first, any of its sub-accounts can receive the claim
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCPendingClaim, DC_PENDING_CLAIM_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{CLAIM_TRANSFER_CIRCUIT_HASH, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_CLAIM_TRANSFER: u32 = comp_def_offset("claim_transfer");
+
+// Init Comp Def
+pub fn init_claim_transfer_comp_def(ctx: Context<InitClaimTransferCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("claim_transfer");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: CLAIM_TRANSFER_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("claim_transfer", payer)]
+#[derive(Accounts)]
+pub struct InitClaimTransferCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_claim_transfer(
+    ctx: Context<QueueClaimTransfer>,
+    computation_offset: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pending_claim.require_claimable(now)?;
+
+    // Lock Pending Claim & Recipient Account until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .pending_claim
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let args = vec![
+        // Pending Claim Amount (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.pending_claim.amount_nonce),
+        Argument::Account(ctx.accounts.pending_claim.key(), 8 + 32, 32),
+        // Recipient Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ClaimTransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.pending_claim.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.pending_claim.sender,
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("claim_transfer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueClaimTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Pending Claim, only the recipient's seeds match
+    #[account(
+        mut,
+        seeds = [
+            DC_PENDING_CLAIM_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &pending_claim.computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+    // Recipient DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
+
+#[event]
+pub struct TransferClaimedEvent {
+    pub status: DCStatus,
+    pub pending_claim: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    // Recipient on claim, sender on reclaim
+    pub claimer: Pubkey,
+    pub new_claimer_balance: [u8; 32],
+}
+
+// Callback Fn
+// Shared by claim & reclaim, the queue ix decides whose account is credited
+pub fn claim_transfer_callback(
+    ctx: Context<ClaimTransferCallback>,
+    output: ComputationOutputs<ClaimTransferOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(ClaimTransferOutput {
+            field_0:
+                ClaimTransferOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_claimer_balance,
+                },
+        }) => (status_code, new_claimer_balance),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(TransferClaimedEvent {
+        status,
+        pending_claim: ctx.accounts.pending_claim.key(),
+        sender: ctx.accounts.pending_claim.sender,
+        recipient: ctx.accounts.pending_claim.recipient,
+        claimer: ctx.accounts.dc_user_token_account.owner,
+        new_claimer_balance: o.1.ciphertexts[0],
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .pending_claim
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    if !status.is_success() {
+        // Pending claim stays open and can be claimed again
+        ctx.accounts.pending_claim.lock.unlock();
+        ctx.accounts.dc_user_token_account.lock.unlock();
+        return Ok(());
+    }
+
+    ctx.accounts.dc_user_token_account.amount = o.1.ciphertexts[0];
+    ctx.accounts.dc_user_token_account.amount_nonce = o.1.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
+
+    // Pending claim is empty, return the rent to the sender
+    ctx.accounts
+        .pending_claim
+        .close(ctx.accounts.sender.to_account_info())?;
+
+    Ok(())
+}
+
+#[callback_accounts("claim_transfer")]
+#[derive(Accounts)]
+pub struct ClaimTransferCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // Pending Claim
+    #[account(mut)]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+    // Claimer DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    /// CHECK: sender, receives the pending claim rent once claimed
+    #[account(
+        mut,
+        address = pending_claim.sender,
+    )]
+    pub sender: AccountInfo<'info>,
+}
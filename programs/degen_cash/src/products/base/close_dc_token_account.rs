@@ -0,0 +1,255 @@
+// Close User DC Token Account
+// The circuit only reveals whether the balance is zero, the account is closed and the
+// rent returned to the owner if it is
+
+use crate::base::ErrorCode;
+use crate::DCUserTokenAccount;
+use crate::SignerAccount;
+use crate::StaleComputationEvent;
+use crate::CLOSE_USER_DC_BALANCE_CIRCUIT_HASH;
+use crate::DC_USER_TOKEN_ACCOUNT_SEED;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+const COMP_DEF_OFFSET_CLOSE_DC_TOKEN_ACCOUNT: u32 = comp_def_offset("close_user_dc_balance");
+
+// Init Comp Def
+pub fn init_close_dc_token_account_comp_def(
+    ctx: Context<InitCloseDCTokenAccountCompDef>,
+) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("close_user_dc_balance");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: CLOSE_USER_DC_BALANCE_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("close_user_dc_balance", payer)]
+#[derive(Accounts)]
+pub struct InitCloseDCTokenAccountCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_close_dc_token_account(
+    ctx: Context<QueueCloseUserDcBalance>,
+    computation_offset: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    // Lock until the callback lands, so the balance can't change before the close
+    ctx.accounts.dc_user_token_account.lock.lock(
+        ctx.accounts.computation_account.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let args = vec![
+        // Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![CloseUserDcBalanceCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.payer.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("close_user_dc_balance", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueCloseUserDcBalance<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLOSE_DC_TOKEN_ACCOUNT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    // DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
+
+#[event]
+pub struct DCTokenAccountClosedEvent {
+    pub dc_user_token_account: Pubkey,
+    pub owner: Pubkey,
+    // False if a balance remains, the account is left open
+    pub closed: bool,
+}
+
+// Callback Fn
+pub fn close_user_dc_balance_callback(
+    ctx: Context<CloseUserDcBalanceCallback>,
+    output: ComputationOutputs<CloseUserDcBalanceOutput>,
+) -> Result<()> {
+    let is_zero = match output {
+        ComputationOutputs::Success(CloseUserDcBalanceOutput { field_0 }) => field_0,
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .dc_user_token_account
+        .lock
+        .is_pending(computation_account)
+    {
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    emit!(DCTokenAccountClosedEvent {
+        dc_user_token_account: ctx.accounts.dc_user_token_account.key(),
+        owner: ctx.accounts.dc_user_token_account.owner,
+        closed: is_zero,
+    });
+
+    if !is_zero {
+        ctx.accounts.dc_user_token_account.lock.unlock();
+        return Ok(());
+    }
+
+    ctx.accounts
+        .dc_user_token_account
+        .close(ctx.accounts.owner.to_account_info())?;
+
+    Ok(())
+}
+
+#[callback_accounts("close_user_dc_balance")]
+#[derive(Accounts)]
+pub struct CloseUserDcBalanceCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLOSE_DC_TOKEN_ACCOUNT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+
+    // DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+
+    /// CHECK: owner, receives the account rent once closed
+    #[account(
+        mut,
+        address = dc_user_token_account.owner,
+    )]
+    pub owner: AccountInfo<'info>,
+}
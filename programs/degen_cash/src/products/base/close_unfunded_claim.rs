@@ -0,0 +1,30 @@
+// Close a pending claim whose send_claimable_transfer computation aborted
+// The callback never landed so the sender was never debited, only the rent is returned
+
+use crate::base::ErrorCode;
+use crate::{DCPendingClaim, DC_PENDING_CLAIM_SEED};
+use anchor_lang::prelude::*;
+
+pub fn close_unfunded_claim(ctx: Context<CloseUnfundedClaim>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pending_claim.require_unfunded(now)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseUnfundedClaim<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            DC_PENDING_CLAIM_SEED.as_bytes(),
+            pending_claim.recipient.as_ref(),
+            &pending_claim.computation_offset.to_le_bytes(),
+        ],
+        bump,
+        constraint = pending_claim.sender == sender.key() @ ErrorCode::PendingClaimNotSender,
+        close = sender,
+    )]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+}
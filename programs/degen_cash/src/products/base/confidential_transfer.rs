@@ -0,0 +1,424 @@
+// Transfer Degen Cash from user to user without revealing the amount
+// The amount is encrypted with the sender's DC account key, the max amount and
+// worst-case checks run in MPC and only the status code is revealed
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::DCStatus;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{
+    DCGlobalMint, DCUserTokenAccount, StaleComputationEvent, CONFIDENTIAL_TRANSFER_CIRCUIT_HASH,
+    DC_GLOBAL_MINT_SEED,
+};
+use crate::{DCOperation, DCOperationReceipt, OperationStatus, DC_OPERATION_RECEIPT_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+// Init Comp Def
+const COMP_DEF_OFFSET_CONFIDENTIAL_TRANSFER: u32 = comp_def_offset("confidential_transfer");
+
+// Init Comp Def
+pub fn init_confidential_transfer_comp_def(
+    ctx: Context<InitConfidentialTransferCompDef>,
+) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("confidential_transfer");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: CONFIDENTIAL_TRANSFER_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("confidential_transfer", payer)]
+#[derive(Accounts)]
+pub struct InitConfidentialTransferCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_confidential_transfer(
+    ctx: Context<QueueConfidentialTransfer>,
+    computation_offset: u64,
+    transfer_amount: [u8; 32],
+    transfer_amount_nonce: u128,
+    max_variance: u8,
+    _reciever_pubkey: Pubkey, // used in constraints
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Transfer)?;
+
+    // Lock Global Mint, Sender & Receiver until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .receiver_dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts
+        .operation_receipt
+        .set_inner(DCOperationReceipt {
+            owner: ctx.accounts.payer.key(),
+            computation_offset,
+            operation: DCOperation::ConfidentialTransfer,
+            counterparty: ctx.accounts.receiver_dc_user_token_account.owner,
+            amount: 0,
+            status: OperationStatus::Pending,
+            circuit_status: DCStatus::Success,
+            queued_at: now,
+            finalized_at: 0,
+        });
+
+    let fee_bps = ctx
+        .accounts
+        .dc_config_account
+        .transfer_fee_bps(max_variance);
+
+    let args = vec![
+        // Global Reserves Balance (u64) - USDC held in the DC vault, used to calculate NAV
+        Argument::PlaintextU64(ctx.accounts.deposit_ata.amount),
+        // Global DC Balance (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
+        Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
+        // Sender Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // Receiver Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.receiver_dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.receiver_dc_user_token_account.amount_nonce),
+        Argument::Account(
+            ctx.accounts.receiver_dc_user_token_account.key(),
+            8 + 32 + 32,
+            32,
+        ),
+        // Transfer Amount (Enc<Shared, u64>) - encrypted with the sender's DC account key
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(transfer_amount_nonce),
+        Argument::EncryptedU64(transfer_amount),
+        // Max Transfer Amount (u64) - checked in MPC against the encrypted amount
+        Argument::PlaintextU64(ctx.accounts.dc_config_account.max_transfer_amount),
+        // Max Variance (u8) (0 - 255) important it's full range otherwise we have modulo bias
+        Argument::PlaintextU8(max_variance),
+        // Fee (u16 bps) from the DC Config fee curve
+        Argument::PlaintextU16(fee_bps),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ConfidentialTransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.receiver_dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.operation_receipt.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("confidential_transfer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, transfer_amount: [u8; 32], transfer_amount_nonce: u128, max_variance: u8, _reciever_pubkey: Pubkey)]
+pub struct QueueConfidentialTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CONFIDENTIAL_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Custom Accounts
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC Config Account (read only -- fees & limits)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+    // DC Deposit ATA (read only -- USDC reserves backing DC)
+    #[account(
+        associated_token::mint = dc_global_mint_account.deposit_mint,
+        associated_token::authority = dc_global_mint_account,
+    )]
+    pub deposit_ata: InterfaceAccount<'info, TokenAccount>,
+    // DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receiver DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            _reciever_pubkey.as_ref(),
+            &DCUserTokenAccount::index_seed(receiver_dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receipt for this computation, finalized in the callback
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCOperationReceipt::INIT_SPACE,
+        seeds = [
+            DC_OPERATION_RECEIPT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_receipt: Box<Account<'info, DCOperationReceipt>>,
+}
+
+#[event]
+pub struct ConfidentialTransferEvent {
+    pub status: DCStatus,
+    pub new_sender_balance: [u8; 32],
+    pub new_global_mint_balance: [u8; 32],
+    pub new_receiver_balance: [u8; 32],
+}
+
+pub fn confidential_transfer_callback(
+    ctx: Context<ConfidentialTransferCallback>,
+    output: ComputationOutputs<ConfidentialTransferOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(ConfidentialTransferOutput {
+            field_0:
+                ConfidentialTransferOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_sender_balance,
+                    field_2: new_global_mint_balance,
+                    field_3: new_receiver_balance,
+                },
+        }) => (
+            status_code,
+            new_sender_balance,
+            new_global_mint_balance,
+            new_receiver_balance,
+        ),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(ConfidentialTransferEvent {
+        status,
+        new_sender_balance: o.1.ciphertexts[0],
+        new_global_mint_balance: o.2.ciphertexts[0],
+        new_receiver_balance: o.3.ciphertexts[0],
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+        && ctx
+            .accounts
+            .receiver_dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        ctx.accounts
+            .operation_receipt
+            .finalize(OperationStatus::Stale, status, now);
+        return Ok(());
+    }
+
+    match status {
+        DCStatus::Success => {
+            ctx.accounts.dc_global_mint_account.supply = o.2.ciphertexts[0];
+            ctx.accounts.dc_global_mint_account.supply_nonce = o.2.nonce;
+            ctx.accounts.dc_user_token_account.amount = o.1.ciphertexts[0];
+            ctx.accounts.dc_user_token_account.amount_nonce = o.1.nonce;
+            ctx.accounts.receiver_dc_user_token_account.amount = o.3.ciphertexts[0];
+            ctx.accounts.receiver_dc_user_token_account.amount_nonce = o.3.nonce;
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Succeeded, status, now);
+            Ok(())
+        }
+        _ => {
+            // leave things as they are if error in arcis
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Failed, status, now);
+            Ok(())
+        }
+    }
+}
+
+// Callback Fn
+#[callback_accounts("confidential_transfer")]
+#[derive(Accounts)]
+pub struct ConfidentialTransferCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CONFIDENTIAL_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // DC Global Mint Account
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receiver DC User Token Account
+    #[account(mut)]
+    pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Operation Receipt
+    #[account(mut)]
+    pub operation_receipt: Account<'info, DCOperationReceipt>,
+}
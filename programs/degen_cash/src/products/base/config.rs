@@ -0,0 +1,130 @@
+// Admin controlled fees and limits for Degen Cash
+// DCConfig is created alongside the global mint in queue_init_global_dc_mint
+
+use crate::base::ErrorCode;
+use crate::{DCConfig, PausableOperation, BPS_DENOMINATOR, DC_CONFIG_SEED};
+use anchor_lang::prelude::*;
+
+pub fn set_withdraw_fee(ctx: Context<UpdateDCConfig>, withdraw_fee_bps: u16) -> Result<()> {
+    if withdraw_fee_bps > BPS_DENOMINATOR {
+        return Err(ErrorCode::InvalidConfig.into());
+    }
+
+    ctx.accounts.dc_config_account.withdraw_fee_bps = withdraw_fee_bps;
+    emit_config_updated(&ctx.accounts.dc_config_account);
+    Ok(())
+}
+
+pub fn set_transfer_fee_curve(
+    ctx: Context<UpdateDCConfig>,
+    transfer_max_fee_bps: u16,
+    transfer_min_fee_bps: u16,
+) -> Result<()> {
+    if transfer_max_fee_bps > BPS_DENOMINATOR || transfer_min_fee_bps > transfer_max_fee_bps {
+        return Err(ErrorCode::InvalidConfig.into());
+    }
+
+    ctx.accounts.dc_config_account.transfer_max_fee_bps = transfer_max_fee_bps;
+    ctx.accounts.dc_config_account.transfer_min_fee_bps = transfer_min_fee_bps;
+    emit_config_updated(&ctx.accounts.dc_config_account);
+    Ok(())
+}
+
+pub fn set_max_transfer_amount(
+    ctx: Context<UpdateDCConfig>,
+    max_transfer_amount: u64,
+) -> Result<()> {
+    if max_transfer_amount == 0
+        || max_transfer_amount < ctx.accounts.dc_config_account.min_deposit_amount
+    {
+        return Err(ErrorCode::InvalidConfig.into());
+    }
+
+    ctx.accounts.dc_config_account.max_transfer_amount = max_transfer_amount;
+    emit_config_updated(&ctx.accounts.dc_config_account);
+    Ok(())
+}
+
+pub fn set_min_deposit_amount(ctx: Context<UpdateDCConfig>, min_deposit_amount: u64) -> Result<()> {
+    // A minimum above the transfer cap would let deposits in that can never be sent
+    if min_deposit_amount > ctx.accounts.dc_config_account.max_transfer_amount {
+        return Err(ErrorCode::InvalidConfig.into());
+    }
+
+    ctx.accounts.dc_config_account.min_deposit_amount = min_deposit_amount;
+    emit_config_updated(&ctx.accounts.dc_config_account);
+    Ok(())
+}
+
+pub fn set_pause_authority(ctx: Context<UpdateDCConfig>, pause_authority: Pubkey) -> Result<()> {
+    ctx.accounts.dc_config_account.pause_authority = pause_authority;
+    emit_config_updated(&ctx.accounts.dc_config_account);
+    Ok(())
+}
+
+// Callbacks are never paused so in flight computations can still finalize
+pub fn set_paused(ctx: Context<PauseDC>, operation: PausableOperation, paused: bool) -> Result<()> {
+    ctx.accounts.dc_config_account.set_paused(operation, paused);
+
+    if paused {
+        emit!(PausedEvent { operation });
+    } else {
+        emit!(UnpausedEvent { operation });
+    }
+    Ok(())
+}
+
+fn emit_config_updated(config: &DCConfig) {
+    emit!(DCConfigUpdatedEvent {
+        withdraw_fee_bps: config.withdraw_fee_bps,
+        transfer_max_fee_bps: config.transfer_max_fee_bps,
+        transfer_min_fee_bps: config.transfer_min_fee_bps,
+        max_transfer_amount: config.max_transfer_amount,
+        min_deposit_amount: config.min_deposit_amount,
+        pause_authority: config.pause_authority,
+    });
+}
+
+#[derive(Accounts)]
+pub struct UpdateDCConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+        has_one = admin,
+    )]
+    pub dc_config_account: Account<'info, DCConfig>,
+}
+
+#[derive(Accounts)]
+pub struct PauseDC<'info> {
+    pub pause_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+        has_one = pause_authority,
+    )]
+    pub dc_config_account: Account<'info, DCConfig>,
+}
+
+#[event]
+pub struct DCConfigUpdatedEvent {
+    pub withdraw_fee_bps: u16,
+    pub transfer_max_fee_bps: u16,
+    pub transfer_min_fee_bps: u16,
+    pub max_transfer_amount: u64,
+    pub min_deposit_amount: u64,
+    pub pause_authority: Pubkey,
+}
+
+#[event]
+pub struct PausedEvent {
+    pub operation: PausableOperation,
+}
+
+#[event]
+pub struct UnpausedEvent {
+    pub operation: PausableOperation,
+}
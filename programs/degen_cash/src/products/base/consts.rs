@@ -1,7 +1,26 @@
 use anchor_lang::prelude::*;
 
 pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
-pub const CIRCUITS_URL: &str = "http://host.docker.internal:3131/";
+// <CIRCUIT>_CIRCUIT_HASH consts generated by build.rs from circuits.sha256
+include!(concat!(env!("OUT_DIR"), "/circuit_hashes.rs"));
 pub const DC_DECIMALS: u8 = 6;
 pub const DC_GLOBAL_MINT_SEED: &str = "dc_global_mint";
 pub const DC_USER_TOKEN_ACCOUNT_SEED: &str = "dc_user_token_account";
+pub const DC_CONFIG_SEED: &str = "dc_config";
+pub const DC_CIRCUIT_CONFIG_SEED: &str = "dc_circuit_config";
+pub const DC_OPERATION_RECEIPT_SEED: &str = "dc_operation_receipt";
+pub const DC_PENDING_CLAIM_SEED: &str = "dc_pending_claim";
+pub const COMPUTATION_LOCK_TIMEOUT_SECS: i64 = 300;
+pub const BPS_DENOMINATOR: u16 = 10_000;
+pub const MAX_VARIANCE: u8 = 255;
+
+// DCConfig defaults set at init
+pub const DEFAULT_WITHDRAW_FEE_BPS: u16 = 50;
+pub const DEFAULT_TRANSFER_MAX_FEE_BPS: u16 = 255;
+pub const DEFAULT_TRANSFER_MIN_FEE_BPS: u16 = 0;
+pub const DEFAULT_MAX_TRANSFER_AMOUNT: u64 = 1_000_000_000;
+pub const DEFAULT_MIN_DEPOSIT_AMOUNT: u64 = 0;
+
+// DCCircuitConfig limits
+pub const MAX_CIRCUITS_URL_LEN: usize = 200;
+pub const MAX_ARTIFACT_SUFFIX_LEN: usize = 32;
@@ -3,8 +3,11 @@
 use crate::base::ErrorCode;
 use crate::DCUserTokenAccount;
 use crate::SignerAccount;
-use crate::CIRCUITS_URL;
+use crate::StaleComputationEvent;
 use crate::DC_USER_TOKEN_ACCOUNT_SEED;
+use crate::INIT_USER_DC_BALANCE_CIRCUIT_HASH;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCConfig, PausableOperation, DC_CONFIG_SEED};
 use crate::{ID, ID_CONST};
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
@@ -20,13 +23,17 @@ const COMP_DEF_OFFSET_CREATE_DC_TOKEN_ACCOUNT: u32 = comp_def_offset("init_user_
 pub fn init_create_dc_token_account_comp_def(
     ctx: Context<InitCreateDCTokenAccountCompDef>,
 ) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("init_user_dc_balance");
     init_comp_def(
         ctx.accounts,
         true,
         0,
         Some(CircuitSource::OffChain(OffChainCircuitSource {
-            source: format!("{}{}", CIRCUITS_URL, "init_user_dc_balance_testnet.arcis").to_string(),
-            hash: [0; 32], // Just use zeros for now - hash verification isn't enforced yet
+            source,
+            hash: INIT_USER_DC_BALANCE_CIRCUIT_HASH,
         })),
         None,
     )?;
@@ -48,6 +55,11 @@ pub struct InitCreateDCTokenAccountCompDef<'info> {
     /// Can't check it here as it's not initialized yet.
     pub comp_def_account: UncheckedAccount<'info>,
     pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -57,13 +69,23 @@ pub fn queue_create_dc_token_account(
     computation_offset: u64,
     owner_x25519: [u8; 32],
     nonce: u128,
+    index: u16,
 ) -> Result<()> {
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::CreateTokenAccount)?;
+
     ctx.accounts.dc_user_token_account.owner = ctx.accounts.payer.key();
     ctx.accounts.dc_user_token_account.amount = [0; 32];
     ctx.accounts.dc_user_token_account.amount_nonce = 0;
     ctx.accounts.dc_user_token_account.owner_x25519 = owner_x25519;
+    ctx.accounts.dc_user_token_account.index = index;
+    ctx.accounts.dc_user_token_account.lock.lock(
+        ctx.accounts.computation_account.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
 
     let args = vec![
         // Enc<Shared, {}> // Used to set 0 with nonce
@@ -76,10 +98,16 @@ pub fn queue_create_dc_token_account(
         computation_offset,
         args,
         None,
-        vec![InitUserDcBalanceCallback::callback_ix(&[CallbackAccount {
-            pubkey: ctx.accounts.dc_user_token_account.key(),
-            is_writable: true,
-        }])],
+        vec![InitUserDcBalanceCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+        ])],
     )?;
 
     Ok(())
@@ -87,7 +115,7 @@ pub fn queue_create_dc_token_account(
 
 #[queue_computation_accounts("init_user_dc_balance", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64)]
+#[instruction(computation_offset: u64, owner_x25519: [u8; 32], nonce: u128, index: u16)]
 pub struct QueueInitUserDcBalance<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -143,12 +171,23 @@ pub struct QueueInitUserDcBalance<'info> {
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
 
+    // DC Config Account (read only -- pause flags)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+
     // DC User Token Account
     #[account(
         init,
         payer = payer,
         space = 8 + DCUserTokenAccount::INIT_SPACE,
-        seeds = [DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(), payer.key().as_ref()],
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(index),
+        ],
         bump,
     )]
     pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
@@ -164,8 +203,22 @@ pub fn init_user_dc_balance_callback(
         _ => return Err(ErrorCode::AbortedComputation.into()),
     };
 
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .dc_user_token_account
+        .lock
+        .is_pending(computation_account)
+    {
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
     ctx.accounts.dc_user_token_account.amount = o.ciphertexts[0];
     ctx.accounts.dc_user_token_account.amount_nonce = o.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
 
     Ok(())
 }
@@ -181,6 +234,8 @@ pub struct InitUserDcBalanceCallback<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
 
     // DC User Token Account
     #[account(mut)]
@@ -1,8 +1,15 @@
 // Deposit USDC to mint Degen Cash
 
 use crate::base::ErrorCode;
-use crate::{DCGlobalMint, DCUserTokenAccount, CIRCUITS_URL, DC_GLOBAL_MINT_SEED};
-use crate::{SignerAccount, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::DCStatus;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{
+    DCGlobalMint, DCUserTokenAccount, StaleComputationEvent, DC_GLOBAL_MINT_SEED,
+    DEPOSIT_CIRCUIT_HASH,
+};
 use crate::{ID, ID_CONST};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -19,13 +26,17 @@ const COMP_DEF_OFFSET_DEPOSIT: u32 = comp_def_offset("deposit");
 
 // Init Comp Def
 pub fn init_deposit_comp_def(ctx: Context<InitDepositCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("deposit");
     init_comp_def(
         ctx.accounts,
         true,
         0,
         Some(CircuitSource::OffChain(OffChainCircuitSource {
-            source: format!("{}{}", CIRCUITS_URL, "deposit_testnet.arcis").to_string(),
-            hash: [0; 32], // Just use zeros for now - hash verification isn't enforced yet
+            source,
+            hash: DEPOSIT_CIRCUIT_HASH,
         })),
         None,
     )?;
@@ -47,6 +58,11 @@ pub struct InitDepositCompDef<'info> {
     /// Can't check it here as it's not initialized yet.
     pub comp_def_account: UncheckedAccount<'info>,
     pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -58,6 +74,26 @@ pub fn queue_deposit(
 ) -> Result<()> {
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Deposit)?;
+
+    if deposit_amount < ctx.accounts.dc_config_account.min_deposit_amount {
+        return Err(ErrorCode::DepositInvalid.into());
+    }
+
+    // Lock Global Mint & User Account until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
     // Transfer Deposit into DC Global ATA
     transfer(
         CpiContext::new(
@@ -92,6 +128,10 @@ pub fn queue_deposit(
         args,
         None,
         vec![DepositCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
             CallbackAccount {
                 pubkey: ctx.accounts.dc_global_mint_account.key(),
                 is_writable: true,
@@ -187,17 +227,28 @@ pub struct QueueDeposit<'info> {
     pub clock_account: Account<'info, ClockAccount>,
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
-    // DC Global Mint Account (read only -- used to check deposit mint key)
+    // DC Global Mint Account
     #[account(
+        mut,
         seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
         bump,
     )]
     pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC Config Account (read only -- fees & limits)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
 
     // DC User Token Account
     #[account(
         mut,
-        seeds = [DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(), payer.key().as_ref()],
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
         bump,
     )]
     pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
@@ -217,6 +268,9 @@ pub struct QueueDeposit<'info> {
     )]
     pub from_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        address = dc_global_mint_account.deposit_mint @ ErrorCode::InvalidDepositMint
+    )]
     pub deposit_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -224,7 +278,7 @@ pub struct QueueDeposit<'info> {
 
 #[event]
 pub struct DepositEvent {
-    pub status_code: u8,
+    pub status: DCStatus,
     pub deposit_amount: u64,
     pub new_global_mint_amount: [u8; 32],
     pub new_user_dc_balance: [u8; 32],
@@ -252,15 +306,40 @@ pub fn deposit_callback(
         ),
         _ => return Err(ErrorCode::AbortedComputation.into()),
     };
+    let status = DCStatus::from_code(o.0);
 
     emit!(DepositEvent {
-        status_code: o.0,
+        status,
         deposit_amount: o.1,
         new_global_mint_amount: o.2.ciphertexts[0],
         new_user_dc_balance: o.3.ciphertexts[0],
     });
 
-    if o.0 != 0 {
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+    }
+
+    if !status.is_success() || !is_pending {
+        if is_pending {
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+        }
+
         // Return Depositted Funds to User
 
         let dc_global_mint_account_signer_seeds = &[
@@ -287,8 +366,10 @@ pub fn deposit_callback(
     // Update DC User Token Account & Global Mint Account
     ctx.accounts.dc_global_mint_account.supply = o.2.ciphertexts[0];
     ctx.accounts.dc_global_mint_account.supply_nonce = o.2.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
     ctx.accounts.dc_user_token_account.amount = o.3.ciphertexts[0];
     ctx.accounts.dc_user_token_account.amount_nonce = o.3.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
 
     Ok(())
 }
@@ -304,6 +385,8 @@ pub struct DepositCallback<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
 
     // DC Global Mint Account
     #[account(
@@ -339,6 +422,9 @@ pub struct DepositCallback<'info> {
     pub dc_deposit_ata: InterfaceAccount<'info, TokenAccount>,
 
     // Read Only Accounts
+    #[account(
+        address = dc_global_mint_account.deposit_mint @ ErrorCode::InvalidDepositMint
+    )]
     pub deposit_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
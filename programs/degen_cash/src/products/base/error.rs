@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+pub use dc_status::DCStatus;
 
 #[error_code]
 pub enum ErrorCode {
@@ -10,10 +11,88 @@ pub enum ErrorCode {
     DepositInvalid,
     #[msg("Max Transfer Amount Exceeded")]
     MaxTransferAmountExceeded,
-    #[msg("Transfer Failed: Insufficient Funds")]
-    TransferInsufficientFunds,
-    #[msg("Transfer Failed: Math Overflow")]
-    TransferMathOverflow,
-    #[msg("Transfer Failed: RNG Failure")]
-    TransferRNGFailure,
+    #[msg("Math Overflow")]
+    MathOverflow,
+    #[msg("Another computation is in flight for this account")]
+    ComputationInFlight,
+    #[msg("Mint does not match the DC deposit mint")]
+    InvalidDepositMint,
+    #[msg("Invalid Config")]
+    InvalidConfig,
+    #[msg("Operation is paused")]
+    OperationPaused,
+    #[msg("Operation has not been finalized")]
+    OperationPending,
+    #[msg("Invalid Escrow")]
+    EscrowInvalid,
+    #[msg("Escrow is not active")]
+    EscrowNotActive,
+    #[msg("Escrow is still locked")]
+    EscrowLocked,
+    #[msg("Escrow has expired")]
+    EscrowExpired,
+    #[msg("Escrow has not expired")]
+    EscrowNotExpired,
+    #[msg("Escrow approval threshold not met")]
+    EscrowThresholdNotMet,
+    #[msg("Signer is not an escrow approver")]
+    EscrowNotApprover,
+    #[msg("Escrow already approved by this approver")]
+    EscrowAlreadyApproved,
+    #[msg("Escrow is hash locked, it can only be claimed with the secret")]
+    EscrowHashLocked,
+    #[msg("Escrow is not hash locked")]
+    EscrowNotHashLocked,
+    #[msg("Escrow is not a dead man's switch")]
+    EscrowNoHeartbeat,
+    #[msg("Escrow deadline has passed")]
+    EscrowDeadlinePassed,
+    #[msg("Invalid Lottery")]
+    LotteryInvalid,
+    #[msg("Lottery is sold out")]
+    LotterySoldOut,
+    #[msg("Lottery is not open")]
+    LotteryNotOpen,
+    #[msg("Lottery has already been drawn")]
+    LotteryAlreadyDrawn,
+    #[msg("Not enough tickets sold to draw")]
+    LotteryNotEnoughTickets,
+    #[msg("Lottery has not been drawn")]
+    LotteryNotDrawn,
+    #[msg("Lottery claim deadline has passed")]
+    LotteryClaimExpired,
+    #[msg("Invalid Lottery Ticket")]
+    LotteryTicketInvalid,
+    #[msg("Lottery ticket has already been redeemed")]
+    LotteryTicketRedeemed,
+    #[msg("Lottery ticket is not a winning ticket")]
+    LotteryTicketNotWinning,
+    #[msg("Lottery claim window is still open")]
+    LotteryClaimWindowOpen,
+    #[msg("Lottery round is still selling tickets")]
+    LotteryRoundNotOver,
+    #[msg("Sub-account transfer needs two different sub-accounts")]
+    SubAccountTransferToSelf,
+    #[msg("Claimable transfer needs a positive claim window")]
+    PendingClaimInvalid,
+    #[msg("Pending claim is not funded")]
+    PendingClaimNotFunded,
+    #[msg("Pending claim has expired")]
+    PendingClaimExpired,
+    #[msg("Pending claim has not expired yet")]
+    PendingClaimNotExpired,
+    #[msg("Only the sender can reclaim a pending claim")]
+    PendingClaimNotSender,
+    #[msg("Only the program upgrade authority can do this")]
+    NotUpgradeAuthority,
+    #[msg("Escrow is already funded")]
+    EscrowFunded,
+    #[msg("Escrow has an expiry or its approval threshold is met, it can't be refunded")]
+    EscrowNotRefundable,
+    #[msg("Pending claim is already funded")]
+    PendingClaimFunded,
+    #[msg("Only the DC admin can do this")]
+    NotAdmin,
+    #[msg("Artifact suffix differs from the one the circuit hashes were built for")]
+    ArtifactSuffixMismatch,
 }
@@ -3,7 +3,15 @@
 
 use crate::base::ErrorCode;
 use crate::SignerAccount;
-use crate::{DCGlobalMint, CIRCUITS_URL, DC_GLOBAL_MINT_SEED};
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, DCGlobalMint, StaleComputationEvent, DC_GLOBAL_MINT_SEED,
+    INIT_GLOBAL_DC_MINT_CIRCUIT_HASH,
+};
+use crate::{
+    DC_CONFIG_SEED, DEFAULT_MAX_TRANSFER_AMOUNT, DEFAULT_MIN_DEPOSIT_AMOUNT,
+    DEFAULT_TRANSFER_MAX_FEE_BPS, DEFAULT_TRANSFER_MIN_FEE_BPS, DEFAULT_WITHDRAW_FEE_BPS,
+};
 use crate::{ID, ID_CONST};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -19,13 +27,17 @@ const COMP_DEF_OFFSET_INIT_GLOBAL_DC_MINT: u32 = comp_def_offset("init_global_dc
 
 // Init Comp Def Fn
 pub fn init_global_dc_mint_comp_def(ctx: Context<InitGlobalDCMintCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("init_global_dc_mint");
     init_comp_def(
         ctx.accounts,
         true,
         0,
         Some(CircuitSource::OffChain(OffChainCircuitSource {
-            source: format!("{}{}", CIRCUITS_URL, "init_global_dc_mint_testnet.arcis").to_string(),
-            hash: [0; 32], // Just use zeros for now - hash verification isn't enforced yet
+            source,
+            hash: INIT_GLOBAL_DC_MINT_CIRCUIT_HASH,
         })),
         None,
     )?;
@@ -47,6 +59,11 @@ pub struct InitGlobalDCMintCompDef<'info> {
     /// Can't check it here as it's not initialized yet.
     pub comp_def_account: UncheckedAccount<'info>,
     pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -59,9 +76,33 @@ pub fn queue_init_global_dc_mint(
 ) -> Result<()> {
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    // Deposit ATA is created for the mint account, so it has to match the stored mint
+    if deposit_mint != ctx.accounts.deposit_mint.key() {
+        return Err(ErrorCode::InvalidDepositMint.into());
+    }
+
+    // Payer of the init becomes the config admin & pause authority
+    ctx.accounts.dc_config_account.set_inner(DCConfig {
+        admin: ctx.accounts.payer.key(),
+        withdraw_fee_bps: DEFAULT_WITHDRAW_FEE_BPS,
+        transfer_max_fee_bps: DEFAULT_TRANSFER_MAX_FEE_BPS,
+        transfer_min_fee_bps: DEFAULT_TRANSFER_MIN_FEE_BPS,
+        max_transfer_amount: DEFAULT_MAX_TRANSFER_AMOUNT,
+        min_deposit_amount: DEFAULT_MIN_DEPOSIT_AMOUNT,
+        pause_authority: ctx.accounts.payer.key(),
+        account_creation_paused: false,
+        deposits_paused: false,
+        transfers_paused: false,
+        withdrawals_paused: false,
+    });
+
     ctx.accounts.dc_global_mint_account.deposit_mint = deposit_mint;
     ctx.accounts.dc_global_mint_account.supply = [0; 32];
     ctx.accounts.dc_global_mint_account.supply_nonce = nonce;
+    ctx.accounts.dc_global_mint_account.lock.lock(
+        ctx.accounts.computation_account.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
 
     let args = vec![Argument::PlaintextU128(nonce)];
     queue_computation(
@@ -69,10 +110,16 @@ pub fn queue_init_global_dc_mint(
         computation_offset,
         args,
         None,
-        vec![InitGlobalDcMintCallback::callback_ix(&[CallbackAccount {
-            pubkey: ctx.accounts.dc_global_mint_account.key(),
-            is_writable: true,
-        }])],
+        vec![InitGlobalDcMintCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+        ])],
     )?;
 
     Ok(())
@@ -144,6 +191,15 @@ pub struct QueueInitGlobalDCMint<'info> {
         bump,
     )]
     pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC Config Account
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCConfig::INIT_SPACE,
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
     // DC ATA
     #[account(
         init,
@@ -167,8 +223,22 @@ pub fn init_global_dc_mint_callback(
         _ => return Err(ErrorCode::AbortedComputation.into()),
     };
 
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+    {
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
     ctx.accounts.dc_global_mint_account.supply = o.ciphertexts[0];
     ctx.accounts.dc_global_mint_account.supply_nonce = o.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
 
     emit!(InitGlobalDcMintEvent {
         encrypted_data: o.ciphertexts[0]
@@ -193,6 +263,8 @@ pub struct InitGlobalDcMintCallback<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
 
     // DC Global Mint Account
     #[account(
@@ -4,18 +4,51 @@ pub use consts::*;
 pub mod init;
 pub use init::*;
 
+pub mod config;
+pub use config::*;
+
+pub mod circuit_config;
+pub use circuit_config::*;
+
 pub mod create_dc_token_account;
 pub use create_dc_token_account::*;
 
+pub mod close_dc_token_account;
+pub use close_dc_token_account::*;
+
+pub mod rotate_dc_key;
+pub use rotate_dc_key::*;
+
 pub mod deposit;
 pub use deposit::*;
 
 pub mod transfer;
 pub use transfer::*;
 
+pub mod confidential_transfer;
+pub use confidential_transfer::*;
+
+pub mod sub_account_transfer;
+pub use sub_account_transfer::*;
+
+pub mod send_claimable_transfer;
+pub use send_claimable_transfer::*;
+
+pub mod claim_transfer;
+pub use claim_transfer::*;
+
+pub mod reclaim_transfer;
+pub use reclaim_transfer::*;
+
+pub mod close_unfunded_claim;
+pub use close_unfunded_claim::*;
+
 pub mod withdraw;
 pub use withdraw::*;
 
+pub mod receipt;
+pub use receipt::*;
+
 pub mod error;
 pub use error::*;
 
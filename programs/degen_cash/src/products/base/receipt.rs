@@ -0,0 +1,34 @@
+// Receipts for queued transfers & withdrawals
+// Created by the queue ix, finalized by the callback, closed by the owner once read
+// An aborted computation never runs its callback, so a receipt still pending once the
+// computation lock has timed out can be closed as well
+
+use crate::base::ErrorCode;
+use crate::{DCOperationReceipt, OperationStatus, COMPUTATION_LOCK_TIMEOUT_SECS};
+use anchor_lang::prelude::*;
+
+pub fn close_operation_receipt(ctx: Context<CloseOperationReceipt>) -> Result<()> {
+    let receipt = &ctx.accounts.operation_receipt;
+    let now = Clock::get()?.unix_timestamp;
+    if receipt.status == OperationStatus::Pending
+        && now
+            < receipt
+                .queued_at
+                .saturating_add(COMPUTATION_LOCK_TIMEOUT_SECS)
+    {
+        return Err(ErrorCode::OperationPending.into());
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseOperationReceipt<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner,
+    )]
+    pub operation_receipt: Account<'info, DCOperationReceipt>,
+}
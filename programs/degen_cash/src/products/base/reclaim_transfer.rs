@@ -0,0 +1,156 @@
+// Reclaim an expired pending claim the recipient never claimed
+// Queued on the claim_transfer circuit, crediting the sender instead of the recipient
+
+// Queue Fn (callback is claim_transfer_callback)
+
+use crate::base::ErrorCode;
+use crate::ClaimTransferCallback;
+use crate::{DCPendingClaim, DC_PENDING_CLAIM_SEED};
+use crate::{DCUserTokenAccount, SignerAccount, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+const COMP_DEF_OFFSET_CLAIM_TRANSFER: u32 = comp_def_offset("claim_transfer");
+
+// Queue Fn
+pub fn queue_reclaim_transfer(
+    ctx: Context<QueueReclaimTransfer>,
+    computation_offset: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pending_claim.require_reclaimable(now)?;
+
+    // Lock Pending Claim & Sender Account until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .pending_claim
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let args = vec![
+        // Pending Claim Amount (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.pending_claim.amount_nonce),
+        Argument::Account(ctx.accounts.pending_claim.key(), 8 + 32, 32),
+        // Sender Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ClaimTransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.pending_claim.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.pending_claim.sender,
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("claim_transfer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueReclaimTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Pending Claim
+    #[account(
+        mut,
+        seeds = [
+            DC_PENDING_CLAIM_SEED.as_bytes(),
+            pending_claim.recipient.as_ref(),
+            &pending_claim.computation_offset.to_le_bytes(),
+        ],
+        bump,
+        constraint = pending_claim.sender == payer.key() @ ErrorCode::PendingClaimNotSender,
+    )]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+    // Sender DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
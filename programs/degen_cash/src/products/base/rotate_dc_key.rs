@@ -0,0 +1,243 @@
+// Rotate DC Key
+// Re-encrypts the balance under a new owner_x25519, the callback swaps amount, nonce
+// and key together so the account is never readable by a mix of old and new keys
+
+use crate::base::ErrorCode;
+use crate::DCUserTokenAccount;
+use crate::SignerAccount;
+use crate::StaleComputationEvent;
+use crate::DC_USER_TOKEN_ACCOUNT_SEED;
+use crate::ROTATE_DC_KEY_CIRCUIT_HASH;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+const COMP_DEF_OFFSET_ROTATE_DC_KEY: u32 = comp_def_offset("rotate_dc_key");
+
+// Init Comp Def
+pub fn init_rotate_dc_key_comp_def(ctx: Context<InitRotateDCKeyCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("rotate_dc_key");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: ROTATE_DC_KEY_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("rotate_dc_key", payer)]
+#[derive(Accounts)]
+pub struct InitRotateDCKeyCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_rotate_dc_key(
+    ctx: Context<QueueRotateDcKey>,
+    computation_offset: u64,
+    new_owner_x25519: [u8; 32],
+    nonce: u128,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    // Lock until the callback lands, so nothing is queued against the old key meanwhile
+    ctx.accounts.dc_user_token_account.lock.lock(
+        ctx.accounts.computation_account.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let args = vec![
+        // Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // New Owner (Enc<Shared, {}>)
+        Argument::ArcisPubkey(new_owner_x25519),
+        Argument::PlaintextU128(nonce),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![RotateDcKeyCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("rotate_dc_key", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueRotateDcKey<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_DC_KEY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    // DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
+
+#[event]
+pub struct DCKeyRotatedEvent {
+    pub dc_user_token_account: Pubkey,
+    pub owner: Pubkey,
+    pub owner_x25519: [u8; 32],
+}
+
+// Callback Fn
+pub fn rotate_dc_key_callback(
+    ctx: Context<RotateDcKeyCallback>,
+    output: ComputationOutputs<RotateDcKeyOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(RotateDcKeyOutput { field_0 }) => field_0,
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .dc_user_token_account
+        .lock
+        .is_pending(computation_account)
+    {
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    let dc_user_token_account = &mut ctx.accounts.dc_user_token_account;
+    dc_user_token_account.owner_x25519 = o.encryption_key;
+    dc_user_token_account.amount = o.ciphertexts[0];
+    dc_user_token_account.amount_nonce = o.nonce;
+    dc_user_token_account.lock.unlock();
+
+    emit!(DCKeyRotatedEvent {
+        dc_user_token_account: dc_user_token_account.key(),
+        owner: dc_user_token_account.owner,
+        owner_x25519: dc_user_token_account.owner_x25519,
+    });
+
+    Ok(())
+}
+
+#[callback_accounts("rotate_dc_key")]
+#[derive(Accounts)]
+pub struct RotateDcKeyCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_DC_KEY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+
+    // DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
@@ -0,0 +1,411 @@
+// Send DC to a wallet that has no DC account yet
+// The amount is debited from the sender into a pending claim keyed by the recipient
+// wallet, see claim_transfer.rs & reclaim_transfer.rs
+// Charged like a transfer (variance, fee curve & NAV), so it is no cheaper than
+// transfer to a recipient that already has an account
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{DCGlobalMint, DC_GLOBAL_MINT_SEED};
+use crate::{DCPendingClaim, PendingClaimStatus, DC_PENDING_CLAIM_SEED};
+use crate::{DCStatus, DCUserTokenAccount, StaleComputationEvent};
+use crate::{ID, ID_CONST, SEND_CLAIMABLE_TRANSFER_CIRCUIT_HASH};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_SEND_CLAIMABLE_TRANSFER: u32 = comp_def_offset("send_claimable_transfer");
+
+// Init Comp Def
+pub fn init_send_claimable_transfer_comp_def(
+    ctx: Context<InitSendClaimableTransferCompDef>,
+) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("send_claimable_transfer");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: SEND_CLAIMABLE_TRANSFER_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("send_claimable_transfer", payer)]
+#[derive(Accounts)]
+pub struct InitSendClaimableTransferCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_send_claimable_transfer(
+    ctx: Context<QueueSendClaimableTransfer>,
+    computation_offset: u64,
+    transfer_amount: u64,
+    max_variance: u8,
+    recipient: Pubkey,
+    claim_window: i64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Transfer)?;
+
+    if transfer_amount > ctx.accounts.dc_config_account.max_transfer_amount {
+        return Err(ErrorCode::MaxTransferAmountExceeded.into());
+    }
+    if claim_window <= 0 {
+        return Err(ErrorCode::PendingClaimInvalid.into());
+    }
+
+    // Lock Global Mint, Sender Account & Pending Claim until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    let expires_at = match now.checked_add(claim_window) {
+        Some(expires_at) => expires_at,
+        None => return Err(ErrorCode::MathOverflow.into()),
+    };
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts.pending_claim.set_inner(DCPendingClaim {
+        sender: ctx.accounts.payer.key(),
+        amount: [0; 32],
+        amount_nonce: 0,
+        lock: Default::default(),
+        recipient,
+        computation_offset,
+        transfer_amount,
+        expires_at,
+        status: PendingClaimStatus::Funding,
+    });
+    ctx.accounts
+        .pending_claim
+        .lock
+        .lock(computation_account, now)?;
+
+    let fee_bps = ctx
+        .accounts
+        .dc_config_account
+        .transfer_fee_bps(max_variance);
+
+    let args = vec![
+        // Global Reserves Balance (u64) - USDC held in the DC vault, used to calculate NAV
+        Argument::PlaintextU64(ctx.accounts.deposit_ata.amount),
+        // Global DC Balance (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
+        Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
+        // Sender Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // Transfer Amount (u64)
+        Argument::PlaintextU64(transfer_amount),
+        // Max Variance (u8)
+        Argument::PlaintextU8(max_variance),
+        // Fee (u16 bps) from the DC Config fee curve
+        Argument::PlaintextU16(fee_bps),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![SendClaimableTransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.pending_claim.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.payer.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("send_claimable_transfer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, transfer_amount: u64, max_variance: u8, recipient: Pubkey)]
+pub struct QueueSendClaimableTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SEND_CLAIMABLE_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC Config Account (read only -- pause flags, fees & limits)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+    // DC Deposit ATA (read only -- USDC reserves backing DC)
+    #[account(
+        associated_token::mint = dc_global_mint_account.deposit_mint,
+        associated_token::authority = dc_global_mint_account,
+    )]
+    pub deposit_ata: InterfaceAccount<'info, TokenAccount>,
+    // Sender DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Pending Claim, keyed by the recipient wallet
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCPendingClaim::INIT_SPACE,
+        seeds = [
+            DC_PENDING_CLAIM_SEED.as_bytes(),
+            recipient.as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+}
+
+#[event]
+pub struct ClaimableTransferSentEvent {
+    pub status: DCStatus,
+    pub pending_claim: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub variance: u8,
+    pub transfer_amount: u64,
+    pub expires_at: i64,
+}
+
+// Callback Fn
+pub fn send_claimable_transfer_callback(
+    ctx: Context<SendClaimableTransferCallback>,
+    output: ComputationOutputs<SendClaimableTransferOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(SendClaimableTransferOutput {
+            field_0:
+                SendClaimableTransferOutputStruct0 {
+                    field_0: status_code,
+                    field_1: variance,
+                    field_2: new_sender_balance,
+                    field_3: new_global_mint_balance,
+                    field_4: claim_amount,
+                },
+        }) => (
+            status_code,
+            variance,
+            new_sender_balance,
+            new_global_mint_balance,
+            claim_amount,
+        ),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(ClaimableTransferSentEvent {
+        status,
+        pending_claim: ctx.accounts.pending_claim.key(),
+        sender: ctx.accounts.pending_claim.sender,
+        recipient: ctx.accounts.pending_claim.recipient,
+        variance: o.1,
+        transfer_amount: ctx.accounts.pending_claim.transfer_amount,
+        expires_at: ctx.accounts.pending_claim.expires_at,
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+        && ctx
+            .accounts
+            .pending_claim
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+    }
+
+    if !status.is_success() || !is_pending {
+        if is_pending {
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+        }
+
+        // Never funded, return the rent to the sender
+        ctx.accounts
+            .pending_claim
+            .close(ctx.accounts.sender.to_account_info())?;
+        return Ok(());
+    }
+
+    ctx.accounts.dc_global_mint_account.supply = o.3.ciphertexts[0];
+    ctx.accounts.dc_global_mint_account.supply_nonce = o.3.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
+    ctx.accounts.dc_user_token_account.amount = o.2.ciphertexts[0];
+    ctx.accounts.dc_user_token_account.amount_nonce = o.2.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
+    ctx.accounts.pending_claim.amount = o.4.ciphertexts[0];
+    ctx.accounts.pending_claim.amount_nonce = o.4.nonce;
+    ctx.accounts.pending_claim.status = PendingClaimStatus::Claimable;
+    ctx.accounts.pending_claim.lock.unlock();
+
+    Ok(())
+}
+
+#[callback_accounts("send_claimable_transfer")]
+#[derive(Accounts)]
+pub struct SendClaimableTransferCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SEND_CLAIMABLE_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // DC Global Mint Account
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // Sender DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Pending Claim
+    #[account(mut)]
+    pub pending_claim: Box<Account<'info, DCPendingClaim>>,
+    /// CHECK: sender, receives the pending claim rent if funding fails
+    #[account(
+        mut,
+        address = pending_claim.sender,
+    )]
+    pub sender: AccountInfo<'info>,
+}
@@ -1,3 +1,8 @@
+use crate::base::ErrorCode;
+use crate::DCStatus;
+use crate::{
+    COMPUTATION_LOCK_TIMEOUT_SECS, MAX_ARTIFACT_SUFFIX_LEN, MAX_CIRCUITS_URL_LEN, MAX_VARIANCE,
+};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -6,6 +11,7 @@ pub struct DCGlobalMint {
     pub deposit_mint: Pubkey,
     pub supply: [u8; 32],
     pub supply_nonce: u128,
+    pub lock: ComputationLock,
 }
 
 #[account]
@@ -15,4 +21,269 @@ pub struct DCUserTokenAccount {
     pub owner_x25519: [u8; 32],
     pub amount: [u8; 32],
     pub amount_nonce: u128,
+    pub lock: ComputationLock,
+    // Sub-account index, 0 is the wallet's primary account
+    pub index: u16,
+}
+
+impl DCUserTokenAccount {
+    // Last seed of the account PDA. Empty for the primary account so it keeps the
+    // [DC_USER_TOKEN_ACCOUNT_SEED, owner] address, the index (u16 LE) for sub-accounts
+    pub fn index_seed(index: u16) -> Vec<u8> {
+        if index == 0 {
+            Vec::new()
+        } else {
+            index.to_le_bytes().to_vec()
+        }
+    }
+}
+
+// Admin tunable fees and limits, passed to the circuits as plaintext so they can
+// change without a redeploy
+#[account]
+#[derive(InitSpace)]
+pub struct DCConfig {
+    pub admin: Pubkey,
+    pub withdraw_fee_bps: u16,
+    // Transfer fee at max_variance = 0
+    pub transfer_max_fee_bps: u16,
+    // Transfer fee at max_variance = 255
+    pub transfer_min_fee_bps: u16,
+    pub max_transfer_amount: u64,
+    pub min_deposit_amount: u64,
+    // Emergency circuit breaker, only blocks queueing new computations
+    pub pause_authority: Pubkey,
+    pub account_creation_paused: bool,
+    pub deposits_paused: bool,
+    pub transfers_paused: bool,
+    pub withdrawals_paused: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PausableOperation {
+    CreateTokenAccount,
+    Deposit,
+    Transfer,
+    Withdraw,
+}
+
+impl DCConfig {
+    pub fn is_paused(&self, operation: PausableOperation) -> bool {
+        match operation {
+            PausableOperation::CreateTokenAccount => self.account_creation_paused,
+            PausableOperation::Deposit => self.deposits_paused,
+            PausableOperation::Transfer => self.transfers_paused,
+            PausableOperation::Withdraw => self.withdrawals_paused,
+        }
+    }
+
+    pub fn set_paused(&mut self, operation: PausableOperation, paused: bool) {
+        match operation {
+            PausableOperation::CreateTokenAccount => self.account_creation_paused = paused,
+            PausableOperation::Deposit => self.deposits_paused = paused,
+            PausableOperation::Transfer => self.transfers_paused = paused,
+            PausableOperation::Withdraw => self.withdrawals_paused = paused,
+        }
+    }
+
+    pub fn require_not_paused(&self, operation: PausableOperation) -> Result<()> {
+        if self.is_paused(operation) {
+            return Err(ErrorCode::OperationPaused.into());
+        }
+        Ok(())
+    }
+
+    // Fee falls linearly from max to min as the sender takes on more variance
+    pub fn transfer_fee_bps(&self, max_variance: u8) -> u16 {
+        let range = (self.transfer_max_fee_bps - self.transfer_min_fee_bps) as u32;
+        let discount = range * max_variance as u32 / MAX_VARIANCE as u32;
+        self.transfer_max_fee_bps - discount as u16
+    }
+}
+
+// Where the Arcium nodes fetch the off chain circuits from, set per deployment so
+// the same binary works on localnet, devnet and mainnet. Only read when a comp def
+// is initialized, the hash pinned alongside the URL is what the nodes verify.
+#[account]
+#[derive(InitSpace)]
+pub struct DCCircuitConfig {
+    pub authority: Pubkey,
+    // e.g. https://degen.cash/circuits/
+    #[max_len(MAX_CIRCUITS_URL_LEN)]
+    pub base_url: String,
+    // e.g. _testnet, appended to the circuit name before .arcis
+    #[max_len(MAX_ARTIFACT_SUFFIX_LEN)]
+    pub artifact_suffix: String,
+}
+
+impl DCCircuitConfig {
+    pub fn circuit_source(&self, circuit: &str) -> String {
+        format!("{}{}{}.arcis", self.base_url, circuit, self.artifact_suffix)
+    }
+}
+
+// Outcome of a queued operation, keyed by owner + computation_offset so wallets and
+// indexers can look up what happened after the fact instead of catching events live
+#[account]
+#[derive(InitSpace)]
+pub struct DCOperationReceipt {
+    pub owner: Pubkey,
+    pub computation_offset: u64,
+    pub operation: DCOperation,
+    // Receiver owner for transfers (the owner itself for sub-account transfers),
+    // default otherwise
+    pub counterparty: Pubkey,
+    // Requested plaintext amount, 0 for confidential transfers
+    pub amount: u64,
+    pub status: OperationStatus,
+    // Circuit outcome, only meaningful once finalized
+    pub circuit_status: DCStatus,
+    pub queued_at: i64,
+    pub finalized_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DCOperation {
+    Transfer,
+    Withdraw,
+    SubAccountTransfer,
+    ConfidentialTransfer,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum OperationStatus {
+    // Queued, callback hasn't landed yet
+    Pending,
+    Succeeded,
+    // Circuit returned a failure status, balances untouched
+    Failed,
+    // Accounts moved on before the callback landed, balances untouched
+    Stale,
+}
+
+impl DCOperationReceipt {
+    pub fn finalize(&mut self, status: OperationStatus, circuit_status: DCStatus, now: i64) {
+        self.status = status;
+        self.circuit_status = circuit_status;
+        self.finalized_at = now;
+    }
+}
+
+// DC sent to a wallet without a DCUserTokenAccount, held until the recipient claims it
+// or the sender takes it back once it expires
+// amount must stay right after sender (read by the circuits at 8 + 32)
+#[account]
+#[derive(InitSpace)]
+pub struct DCPendingClaim {
+    pub sender: Pubkey,
+    pub amount: [u8; 32],
+    pub amount_nonce: u128,
+    pub lock: ComputationLock,
+    // Wallet allowed to claim, once it has a DCUserTokenAccount
+    pub recipient: Pubkey,
+    // Offset of the send computation, last PDA seed
+    pub computation_offset: u64,
+    // Requested plaintext amount, 0 for confidential transfers
+    pub transfer_amount: u64,
+    // Claimable by the recipient until, reclaimable by the sender from
+    pub expires_at: i64,
+    pub status: PendingClaimStatus,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum PendingClaimStatus {
+    // send_claimable_transfer computation in flight
+    Funding,
+    // Funded, waiting for the recipient
+    Claimable,
+}
+
+impl DCPendingClaim {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn require_claimable(&self, now: i64) -> Result<()> {
+        if self.status != PendingClaimStatus::Claimable {
+            return Err(ErrorCode::PendingClaimNotFunded.into());
+        }
+        if self.is_expired(now) {
+            return Err(ErrorCode::PendingClaimExpired.into());
+        }
+        Ok(())
+    }
+
+    pub fn require_reclaimable(&self, now: i64) -> Result<()> {
+        if self.status != PendingClaimStatus::Claimable {
+            return Err(ErrorCode::PendingClaimNotFunded.into());
+        }
+        if !self.is_expired(now) {
+            return Err(ErrorCode::PendingClaimNotExpired.into());
+        }
+        Ok(())
+    }
+
+    // send_claimable_transfer callback never landed (aborted), the sender was never debited
+    pub fn require_unfunded(&self, now: i64) -> Result<()> {
+        if self.status != PendingClaimStatus::Funding {
+            return Err(ErrorCode::PendingClaimFunded.into());
+        }
+        if self.lock.is_locked(now) {
+            return Err(ErrorCode::ComputationInFlight.into());
+        }
+        Ok(())
+    }
+}
+
+// Ciphertexts are read at queue time and overwritten in the callback, so only one
+// computation may be in flight per account. The callback checks it is still the
+// pending computation before writing anything back.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ComputationLock {
+    // Computation account of the in flight computation (default = unlocked)
+    pub pending_computation: Pubkey,
+    pub locked_at: i64,
+}
+
+impl ComputationLock {
+    // Locks expire so a computation that never returns can't brick the account
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.pending_computation != Pubkey::default()
+            && now < self.locked_at.saturating_add(COMPUTATION_LOCK_TIMEOUT_SECS)
+    }
+
+    pub fn lock(&mut self, computation_account: Pubkey, now: i64) -> Result<()> {
+        if self.is_locked(now) {
+            return Err(ErrorCode::ComputationInFlight.into());
+        }
+        self.pending_computation = computation_account;
+        self.locked_at = now;
+        Ok(())
+    }
+
+    pub fn is_pending(&self, computation_account: Pubkey) -> bool {
+        self.pending_computation != Pubkey::default()
+            && self.pending_computation == computation_account
+    }
+
+    // Computation returned, whether or not it updated the ciphertexts
+    pub fn unlock(&mut self) {
+        self.pending_computation = Pubkey::default();
+        self.locked_at = 0;
+    }
+}
+
+// Wallet a claim proof is made out to, the first 16 bytes of its key (u128 LE). Passed
+// to the circuits as plaintext and compared to the tag encrypted inside the proof, which
+// only the holder of the x25519 secret can produce, so a proof copied from a pending
+// transaction fails for any other signer
+pub fn claimer_tag(wallet: &Pubkey) -> u128 {
+    let mut tag = [0u8; 16];
+    tag.copy_from_slice(&wallet.as_ref()[..16]);
+    u128::from_le_bytes(tag)
+}
+
+#[event]
+pub struct StaleComputationEvent {
+    pub computation_account: Pubkey,
 }
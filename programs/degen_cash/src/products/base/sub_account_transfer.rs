@@ -0,0 +1,359 @@
+// Transfer Degen Cash between sub-accounts of the same wallet
+// Skips the variance, fee & NAV logic of transfer, the global supply is untouched
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::DCStatus;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{DCOperation, DCOperationReceipt, OperationStatus, DC_OPERATION_RECEIPT_SEED};
+use crate::{DCUserTokenAccount, StaleComputationEvent, SUB_ACCOUNT_TRANSFER_CIRCUIT_HASH};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+// Init Comp Def
+const COMP_DEF_OFFSET_SUB_ACCOUNT_TRANSFER: u32 = comp_def_offset("sub_account_transfer");
+
+// Init Comp Def
+pub fn init_sub_account_transfer_comp_def(
+    ctx: Context<InitSubAccountTransferCompDef>,
+) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("sub_account_transfer");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: SUB_ACCOUNT_TRANSFER_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("sub_account_transfer", payer)]
+#[derive(Accounts)]
+pub struct InitSubAccountTransferCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_sub_account_transfer(
+    ctx: Context<QueueSubAccountTransfer>,
+    computation_offset: u64,
+    transfer_amount: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Transfer)?;
+
+    // Lock Sender & Receiver until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .receiver_dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts
+        .operation_receipt
+        .set_inner(DCOperationReceipt {
+            owner: ctx.accounts.payer.key(),
+            computation_offset,
+            operation: DCOperation::SubAccountTransfer,
+            counterparty: ctx.accounts.payer.key(),
+            amount: transfer_amount,
+            status: OperationStatus::Pending,
+            circuit_status: DCStatus::Success,
+            queued_at: now,
+            finalized_at: 0,
+        });
+
+    let args = vec![
+        // Sender Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // Receiver Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.receiver_dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.receiver_dc_user_token_account.amount_nonce),
+        Argument::Account(
+            ctx.accounts.receiver_dc_user_token_account.key(),
+            8 + 32 + 32,
+            32,
+        ),
+        // Transfer Amount (u64)
+        Argument::PlaintextU64(transfer_amount),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![SubAccountTransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.receiver_dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.operation_receipt.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("sub_account_transfer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueSubAccountTransfer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SUB_ACCOUNT_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // DC Config Account (read only -- pause flags)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+    // Sender DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receiver DC User Token Account, another sub-account of the payer
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(receiver_dc_user_token_account.index),
+        ],
+        bump,
+        constraint = receiver_dc_user_token_account.index != dc_user_token_account.index
+            @ ErrorCode::SubAccountTransferToSelf,
+    )]
+    pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receipt for this computation, finalized in the callback
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCOperationReceipt::INIT_SPACE,
+        seeds = [
+            DC_OPERATION_RECEIPT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_receipt: Box<Account<'info, DCOperationReceipt>>,
+}
+
+#[event]
+pub struct SubAccountTransferEvent {
+    pub status: DCStatus,
+    pub owner: Pubkey,
+    pub sender_index: u16,
+    pub receiver_index: u16,
+    pub transfer_amount: u64,
+}
+
+pub fn sub_account_transfer_callback(
+    ctx: Context<SubAccountTransferCallback>,
+    output: ComputationOutputs<SubAccountTransferOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(SubAccountTransferOutput {
+            field_0:
+                SubAccountTransferOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_sender_balance,
+                    field_2: new_receiver_balance,
+                },
+        }) => (status_code, new_sender_balance, new_receiver_balance),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(SubAccountTransferEvent {
+        status,
+        owner: ctx.accounts.dc_user_token_account.owner,
+        sender_index: ctx.accounts.dc_user_token_account.index,
+        receiver_index: ctx.accounts.receiver_dc_user_token_account.index,
+        transfer_amount: ctx.accounts.operation_receipt.amount,
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    let is_pending = ctx
+        .accounts
+        .dc_user_token_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .receiver_dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        ctx.accounts
+            .operation_receipt
+            .finalize(OperationStatus::Stale, status, now);
+        return Ok(());
+    }
+
+    match status {
+        DCStatus::Success => {
+            ctx.accounts.dc_user_token_account.amount = o.1.ciphertexts[0];
+            ctx.accounts.dc_user_token_account.amount_nonce = o.1.nonce;
+            ctx.accounts.receiver_dc_user_token_account.amount = o.2.ciphertexts[0];
+            ctx.accounts.receiver_dc_user_token_account.amount_nonce = o.2.nonce;
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Succeeded, status, now);
+            Ok(())
+        }
+        _ => {
+            // leave things as they are if error in arcis
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Failed, status, now);
+            Ok(())
+        }
+    }
+}
+
+// Callback Fn
+#[callback_accounts("sub_account_transfer")]
+#[derive(Accounts)]
+pub struct SubAccountTransferCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SUB_ACCOUNT_TRANSFER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receiver DC User Token Account
+    #[account(mut)]
+    pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Operation Receipt
+    #[account(mut)]
+    pub operation_receipt: Account<'info, DCOperationReceipt>,
+}
@@ -5,11 +5,19 @@
 // Callback Fn
 
 use crate::base::ErrorCode;
-use crate::{DCGlobalMint, DCUserTokenAccount, CIRCUITS_URL, DC_GLOBAL_MINT_SEED};
-use crate::{SignerAccount, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::DCStatus;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{
+    DCGlobalMint, DCUserTokenAccount, StaleComputationEvent, DC_GLOBAL_MINT_SEED,
+    TRANSFER_CIRCUIT_HASH,
+};
+use crate::{DCOperation, DCOperationReceipt, OperationStatus, DC_OPERATION_RECEIPT_SEED};
 use crate::{ID, ID_CONST};
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::TokenAccount;
 use arcium_anchor::prelude::*;
 use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
 
@@ -18,13 +26,17 @@ const COMP_DEF_OFFSET_TRANSFER: u32 = comp_def_offset("transfer");
 
 // Init Comp Def
 pub fn init_transfer_comp_def(ctx: Context<InitTransferCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("transfer");
     init_comp_def(
         ctx.accounts,
         true,
         0,
         Some(CircuitSource::OffChain(OffChainCircuitSource {
-            source: format!("{}{}", CIRCUITS_URL, "transfer_testnet.arcis").to_string(),
-            hash: [0; 32], // Just use zeros for now - hash verification isn't enforced yet
+            source,
+            hash: TRANSFER_CIRCUIT_HASH,
         })),
         None,
     )?;
@@ -46,6 +58,11 @@ pub struct InitTransferCompDef<'info> {
     /// Can't check it here as it's not initialized yet.
     pub comp_def_account: UncheckedAccount<'info>,
     pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -59,13 +76,52 @@ pub fn queue_transfer(
 ) -> Result<()> {
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
-    if transfer_amount > 1000_000000 {
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Transfer)?;
+
+    if transfer_amount > ctx.accounts.dc_config_account.max_transfer_amount {
         return Err(ErrorCode::MaxTransferAmountExceeded.into());
     }
 
+    // Lock Global Mint, Sender & Receiver until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .receiver_dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts
+        .operation_receipt
+        .set_inner(DCOperationReceipt {
+            owner: ctx.accounts.payer.key(),
+            computation_offset,
+            operation: DCOperation::Transfer,
+            counterparty: ctx.accounts.receiver_dc_user_token_account.owner,
+            amount: transfer_amount,
+            status: OperationStatus::Pending,
+            circuit_status: DCStatus::Success,
+            queued_at: now,
+            finalized_at: 0,
+        });
+
+    let fee_bps = ctx
+        .accounts
+        .dc_config_account
+        .transfer_fee_bps(max_variance);
+
     let args = vec![
-        // Global Reserves Balance (u64) - used to calculate NAV
-        Argument::PlaintextU64(ctx.accounts.deposit_mint.supply),
+        // Global Reserves Balance (u64) - USDC held in the DC vault, used to calculate NAV
+        Argument::PlaintextU64(ctx.accounts.deposit_ata.amount),
         // Global DC Balance (Enc<Mxe, u64>)
         Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
         Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
@@ -85,6 +141,8 @@ pub fn queue_transfer(
         Argument::PlaintextU64(transfer_amount),
         // Max Variance (u8) (0 - 255) important it's full range otherwise we have modulo bias
         Argument::PlaintextU8(max_variance),
+        // Fee (u16 bps) from the DC Config fee curve
+        Argument::PlaintextU16(fee_bps),
     ];
 
     queue_computation(
@@ -93,6 +151,10 @@ pub fn queue_transfer(
         args,
         None,
         vec![TransferCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
             CallbackAccount {
                 pubkey: ctx.accounts.dc_global_mint_account.key(),
                 is_writable: true,
@@ -105,6 +167,14 @@ pub fn queue_transfer(
                 pubkey: ctx.accounts.receiver_dc_user_token_account.key(),
                 is_writable: true,
             },
+            CallbackAccount {
+                pubkey: ctx.accounts.operation_receipt.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.deposit_ata.key(),
+                is_writable: false,
+            },
         ])],
     )?;
 
@@ -170,33 +240,66 @@ pub struct QueueTransfer<'info> {
     pub arcium_program: Program<'info, Arcium>,
     // Custom Accounts
     #[account(
+        mut,
         seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
         bump,
     )]
     pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // DC Config Account (read only -- fees & limits)
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+    // DC Deposit ATA (read only -- USDC reserves backing DC)
     #[account(
-        address = dc_global_mint_account.deposit_mint
+        associated_token::mint = dc_global_mint_account.deposit_mint,
+        associated_token::authority = dc_global_mint_account,
     )]
-    pub deposit_mint: Account<'info, Mint>,
+    pub deposit_ata: InterfaceAccount<'info, TokenAccount>,
     // DC User Token Account
     #[account(
-        seeds = [DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(), payer.key().as_ref()],
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
         bump,
     )]
     pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
     // Receiver DC User Token Account
     #[account(
-        seeds = [DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(), _reciever_pubkey.as_ref()],
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            _reciever_pubkey.as_ref(),
+            &DCUserTokenAccount::index_seed(receiver_dc_user_token_account.index),
+        ],
         bump,
     )]
     pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Receipt for this computation, finalized in the callback
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCOperationReceipt::INIT_SPACE,
+        seeds = [
+            DC_OPERATION_RECEIPT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_receipt: Box<Account<'info, DCOperationReceipt>>,
 }
 
 #[event]
 pub struct TransferEvent {
-    pub status_code: u8,
+    pub status: DCStatus,
     pub variance: u8,
     pub transfer_amount: u64,
+    pub global_reserves: u64,
     pub new_sender_balance: [u8; 32],
     pub new_global_mint_balance: [u8; 32],
     pub new_receiver_balance: [u8; 32],
@@ -227,27 +330,73 @@ pub fn transfer_callback(
         ),
         _ => return Err(ErrorCode::AbortedComputation.into()),
     };
+    let status = DCStatus::from_code(o.0);
 
     emit!(TransferEvent {
-        status_code: o.0,
+        status,
         variance: o.1,
         transfer_amount: o.2,
+        global_reserves: ctx.accounts.deposit_ata.amount,
         new_sender_balance: o.3.ciphertexts[0],
         new_global_mint_balance: o.4.ciphertexts[0],
         new_receiver_balance: o.5.ciphertexts[0],
     });
 
-    match o.0 {
-        0 => {
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+        && ctx
+            .accounts
+            .receiver_dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        ctx.accounts
+            .operation_receipt
+            .finalize(OperationStatus::Stale, status, now);
+        return Ok(());
+    }
+
+    match status {
+        DCStatus::Success => {
             ctx.accounts.dc_global_mint_account.supply = o.4.ciphertexts[0];
             ctx.accounts.dc_global_mint_account.supply_nonce = o.4.nonce;
             ctx.accounts.dc_user_token_account.amount = o.3.ciphertexts[0];
             ctx.accounts.dc_user_token_account.amount_nonce = o.3.nonce;
             ctx.accounts.receiver_dc_user_token_account.amount = o.5.ciphertexts[0];
             ctx.accounts.receiver_dc_user_token_account.amount_nonce = o.5.nonce;
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Succeeded, status, now);
+            Ok(())
+        }
+        _ => {
+            // leave things as they are if error in arcis
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.receiver_dc_user_token_account.lock.unlock();
+            ctx.accounts
+                .operation_receipt
+                .finalize(OperationStatus::Failed, status, now);
             Ok(())
         }
-        _ => Ok(()), // leave things as they are if error in arcis
     }
 }
 
@@ -263,6 +412,8 @@ pub struct TransferCallback<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
     // DC Global Mint Account
     #[account(
         mut,
@@ -276,4 +427,13 @@ pub struct TransferCallback<'info> {
     // Receiver DC User Token Account
     #[account(mut)]
     pub receiver_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Operation Receipt
+    #[account(mut)]
+    pub operation_receipt: Account<'info, DCOperationReceipt>,
+    // DC Deposit ATA (read only -- emitted as the USDC reserves)
+    #[account(
+        associated_token::mint = dc_global_mint_account.deposit_mint,
+        associated_token::authority = dc_global_mint_account,
+    )]
+    pub deposit_ata: InterfaceAccount<'info, TokenAccount>,
 }
@@ -1,6 +1,14 @@
 use crate::base::ErrorCode;
-use crate::{DCGlobalMint, DCUserTokenAccount, CIRCUITS_URL, DC_GLOBAL_MINT_SEED};
-use crate::{SignerAccount, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::DCStatus;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{
+    DCConfig, PausableOperation, SignerAccount, DC_CONFIG_SEED, DC_USER_TOKEN_ACCOUNT_SEED,
+};
+use crate::{
+    DCGlobalMint, DCUserTokenAccount, StaleComputationEvent, DC_GLOBAL_MINT_SEED,
+    WITHDRAW_CIRCUIT_HASH,
+};
+use crate::{DCOperation, DCOperationReceipt, OperationStatus, DC_OPERATION_RECEIPT_SEED};
 use crate::{ID, ID_CONST};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -12,13 +20,17 @@ use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChain
 const COMP_DEF_OFFSET_WITHDRAW: u32 = comp_def_offset("withdraw");
 
 pub fn init_withdraw_comp_def(ctx: Context<InitWithdrawCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("withdraw");
     init_comp_def(
         ctx.accounts,
         true,
         0,
         Some(CircuitSource::OffChain(OffChainCircuitSource {
-            source: format!("{}{}", CIRCUITS_URL, "withdraw_testnet.arcis").to_string(),
-            hash: [0; 32],
+            source,
+            hash: WITHDRAW_CIRCUIT_HASH,
         })),
         None,
     )?;
@@ -39,6 +51,11 @@ pub struct InitWithdrawCompDef<'info> {
     /// CHECK: comp_def_account, checked by arcium program.
     pub comp_def_account: UncheckedAccount<'info>,
     pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -49,6 +66,36 @@ pub fn queue_withdraw(
 ) -> Result<()> {
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
+    ctx.accounts
+        .dc_config_account
+        .require_not_paused(PausableOperation::Withdraw)?;
+
+    // Lock Global Mint & User Account until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts
+        .operation_receipt
+        .set_inner(DCOperationReceipt {
+            owner: ctx.accounts.payer.key(),
+            computation_offset,
+            operation: DCOperation::Withdraw,
+            counterparty: Pubkey::default(),
+            amount: withdraw_amount,
+            status: OperationStatus::Pending,
+            circuit_status: DCStatus::Success,
+            queued_at: now,
+            finalized_at: 0,
+        });
+
     let args = vec![
         Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
         Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
@@ -56,6 +103,7 @@ pub fn queue_withdraw(
         Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
         Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
         Argument::PlaintextU64(withdraw_amount),
+        Argument::PlaintextU16(ctx.accounts.dc_config_account.withdraw_fee_bps),
     ];
 
     queue_computation(
@@ -64,6 +112,10 @@ pub fn queue_withdraw(
         args,
         None,
         vec![WithdrawCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
             CallbackAccount {
                 pubkey: ctx.accounts.dc_global_mint_account.key(),
                 is_writable: true,
@@ -96,6 +148,10 @@ pub fn queue_withdraw(
                 pubkey: ctx.accounts.associated_token_program.key(),
                 is_writable: false,
             },
+            CallbackAccount {
+                pubkey: ctx.accounts.operation_receipt.key(),
+                is_writable: true,
+            },
         ])],
     )?;
 
@@ -160,14 +216,25 @@ pub struct QueueWithdraw<'info> {
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
     #[account(
+        mut,
         seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
         bump,
     )]
     pub dc_global_mint_account: Account<'info, DCGlobalMint>,
 
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+
     #[account(
         mut,
-        seeds = [DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(), payer.key().as_ref()],
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
         bump,
     )]
     pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
@@ -186,14 +253,30 @@ pub struct QueueWithdraw<'info> {
     )]
     pub to_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        address = dc_global_mint_account.deposit_mint @ ErrorCode::InvalidDepositMint
+    )]
     pub withdraw_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCOperationReceipt::INIT_SPACE,
+        seeds = [
+            DC_OPERATION_RECEIPT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub operation_receipt: Box<Account<'info, DCOperationReceipt>>,
 }
 
 #[event]
 pub struct WithdrawEvent {
-    pub status_code: u8,
+    pub status: DCStatus,
     pub withdraw_amount: u64,
     pub new_global_mint_amount: [u8; 32],
     pub new_user_dc_balance: [u8; 32],
@@ -220,15 +303,45 @@ pub fn withdraw_callback(
         ),
         _ => return Err(ErrorCode::AbortedComputation.into()),
     };
+    let status = DCStatus::from_code(o.0);
 
     emit!(WithdrawEvent {
-        status_code: o.0,
+        status,
         withdraw_amount: o.1,
         new_global_mint_amount: o.2.ciphertexts[0],
         new_user_dc_balance: o.3.ciphertexts[0],
     });
 
-    if o.0 != 0 {
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        ctx.accounts
+            .operation_receipt
+            .finalize(OperationStatus::Stale, status, now);
+        return Ok(());
+    }
+
+    if !status.is_success() {
+        ctx.accounts.dc_global_mint_account.lock.unlock();
+        ctx.accounts.dc_user_token_account.lock.unlock();
+        ctx.accounts
+            .operation_receipt
+            .finalize(OperationStatus::Failed, status, now);
         return Ok(());
     }
 
@@ -252,8 +365,13 @@ pub fn withdraw_callback(
 
     ctx.accounts.dc_global_mint_account.supply = o.2.ciphertexts[0];
     ctx.accounts.dc_global_mint_account.supply_nonce = o.2.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
     ctx.accounts.dc_user_token_account.amount = o.3.ciphertexts[0];
     ctx.accounts.dc_user_token_account.amount_nonce = o.3.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
+    ctx.accounts
+        .operation_receipt
+        .finalize(OperationStatus::Succeeded, status, now);
 
     Ok(())
 }
@@ -269,6 +387,8 @@ pub struct WithdrawCallback<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     /// CHECK: instructions_sysvar, checked by the account constraint
     pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
 
     #[account(
         mut,
@@ -297,7 +417,13 @@ pub struct WithdrawCallback<'info> {
     )]
     pub dc_withdraw_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        address = dc_global_mint_account.deposit_mint @ ErrorCode::InvalidDepositMint
+    )]
     pub deposit_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(mut)]
+    pub operation_receipt: Account<'info, DCOperationReceipt>,
 }
@@ -0,0 +1,50 @@
+// Record an approval for an M-of-N escrow
+// No Arcium compute, release checks the approval count against the threshold
+
+use crate::base::ErrorCode;
+use crate::{DCEscrow, EscrowStatus, DC_ESCROW_SEED};
+use anchor_lang::prelude::*;
+
+pub fn approve_escrow(ctx: Context<ApproveEscrow>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow_account;
+
+    if escrow.status != EscrowStatus::Active {
+        return Err(ErrorCode::EscrowNotActive.into());
+    }
+    if escrow.is_expired(now) {
+        return Err(ErrorCode::EscrowExpired.into());
+    }
+    escrow.approve(ctx.accounts.approver.key())?;
+
+    emit!(EscrowApprovedEvent {
+        escrow: escrow.key(),
+        approver: ctx.accounts.approver.key(),
+        approvals: escrow.approval_count(),
+        threshold: escrow.threshold,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveEscrow<'info> {
+    pub approver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            escrow_account.creator.as_ref(),
+            &escrow_account.escrow_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+}
+
+#[event]
+pub struct EscrowApprovedEvent {
+    pub escrow: Pubkey,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
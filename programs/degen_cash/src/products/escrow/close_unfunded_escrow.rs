@@ -0,0 +1,38 @@
+// Close an escrow whose create_escrow computation aborted
+// The callback never landed so the creator was never debited, only the rent is returned
+
+use crate::{DCEscrow, DC_ESCROW_SEED};
+use anchor_lang::prelude::*;
+
+pub fn close_unfunded_escrow(ctx: Context<CloseUnfundedEscrow>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.escrow_account.require_unfunded(now)?;
+
+    emit!(EscrowClosedEvent {
+        escrow: ctx.accounts.escrow_account.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseUnfundedEscrow<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            creator.key().as_ref(),
+            &escrow_account.escrow_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+        close = creator,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+}
+
+#[event]
+pub struct EscrowClosedEvent {
+    pub escrow: Pubkey,
+}
@@ -0,0 +1,3 @@
+pub const DC_ESCROW_SEED: &str = "dc_escrow";
+// Approvals are tracked in a u16 bitmap
+pub const MAX_ESCROW_APPROVERS: usize = 10;
@@ -0,0 +1,330 @@
+// Create an escrow, moving DC from the creator's account into an MXE owned balance
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCEscrow, EscrowStatus, EscrowTerms, DC_ESCROW_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{CREATE_ESCROW_CIRCUIT_HASH, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_CREATE_ESCROW: u32 = comp_def_offset("create_escrow");
+
+// Init Comp Def
+pub fn init_create_escrow_comp_def(ctx: Context<InitCreateEscrowCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("create_escrow");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: CREATE_ESCROW_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("create_escrow", payer)]
+#[derive(Accounts)]
+pub struct InitCreateEscrowCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_create_escrow(
+    ctx: Context<QueueCreateEscrow>,
+    computation_offset: u64,
+    escrow_id: u64,
+    amount: u64,
+    terms: EscrowTerms,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    if amount == 0 {
+        return Err(ErrorCode::EscrowInvalid.into());
+    }
+    terms.validate()?;
+
+    // Lock Creator Account & Escrow until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts.escrow_account.set_inner(DCEscrow {
+        creator: ctx.accounts.payer.key(),
+        amount: [0; 32],
+        amount_nonce: 0,
+        lock: Default::default(),
+        escrow_id,
+        beneficiary: terms.beneficiary,
+        unlock_at: terms.unlock_at,
+        status: EscrowStatus::Funding,
+        expires_at: terms.expires_at,
+        approvers: terms.approvers,
+        threshold: terms.threshold,
+        approvals: 0,
+        hash_lock: terms.hash_lock,
+        heartbeat_interval: terms.heartbeat_interval,
+    });
+    ctx.accounts
+        .escrow_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let args = vec![
+        // Creator Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // Escrow Amount (u64)
+        Argument::PlaintextU64(amount),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![CreateEscrowCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.escrow_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.payer.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("create_escrow", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, escrow_id: u64)]
+pub struct QueueCreateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Creator DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Escrow Account
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCEscrow::INIT_SPACE,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &escrow_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+}
+
+#[event]
+pub struct EscrowCreatedEvent {
+    pub status: DCStatus,
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub unlock_at: i64,
+    pub expires_at: i64,
+    pub threshold: u8,
+}
+
+// Callback Fn
+pub fn create_escrow_callback(
+    ctx: Context<CreateEscrowCallback>,
+    output: ComputationOutputs<CreateEscrowOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(CreateEscrowOutput {
+            field_0:
+                CreateEscrowOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_creator_balance,
+                    field_2: escrow_amount,
+                },
+        }) => (status_code, new_creator_balance, escrow_amount),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(EscrowCreatedEvent {
+        status,
+        escrow: ctx.accounts.escrow_account.key(),
+        creator: ctx.accounts.escrow_account.creator,
+        beneficiary: ctx.accounts.escrow_account.beneficiary,
+        unlock_at: ctx.accounts.escrow_account.unlock_at,
+        expires_at: ctx.accounts.escrow_account.expires_at,
+        threshold: ctx.accounts.escrow_account.threshold,
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .escrow_account
+        .lock
+        .is_pending(computation_account)
+        || !ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+    {
+        // Accounts moved on since queue (lock timed out), don't touch them. The escrow
+        // may have been closed and re-created under the same id by a newer computation
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    if !status.is_success() {
+        ctx.accounts.dc_user_token_account.lock.unlock();
+
+        // Never funded, return the rent to the creator
+        ctx.accounts
+            .escrow_account
+            .close(ctx.accounts.creator.to_account_info())?;
+        return Ok(());
+    }
+
+    ctx.accounts.dc_user_token_account.amount = o.1.ciphertexts[0];
+    ctx.accounts.dc_user_token_account.amount_nonce = o.1.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
+    ctx.accounts.escrow_account.amount = o.2.ciphertexts[0];
+    ctx.accounts.escrow_account.amount_nonce = o.2.nonce;
+    ctx.accounts.escrow_account.status = EscrowStatus::Active;
+    ctx.accounts.escrow_account.lock.unlock();
+
+    Ok(())
+}
+
+#[callback_accounts("create_escrow")]
+#[derive(Accounts)]
+pub struct CreateEscrowCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CREATE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // Creator DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    // Escrow Account
+    #[account(mut)]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+    /// CHECK: creator, receives the escrow rent if funding fails
+    #[account(
+        mut,
+        address = escrow_account.creator,
+    )]
+    pub creator: AccountInfo<'info>,
+}
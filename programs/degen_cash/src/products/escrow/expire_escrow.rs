@@ -0,0 +1,288 @@
+// Burn an escrow that expired without being released
+// Permissionless, the balance leaves the global supply so every holder's share is worth more
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCEscrow, DC_ESCROW_SEED};
+use crate::{DCGlobalMint, DC_GLOBAL_MINT_SEED, EXPIRE_ESCROW_CIRCUIT_HASH};
+use crate::{DCStatus, SignerAccount, StaleComputationEvent};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_EXPIRE_ESCROW: u32 = comp_def_offset("expire_escrow");
+
+// Init Comp Def
+pub fn init_expire_escrow_comp_def(ctx: Context<InitExpireEscrowCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("expire_escrow");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: EXPIRE_ESCROW_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("expire_escrow", payer)]
+#[derive(Accounts)]
+pub struct InitExpireEscrowCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_expire_escrow(ctx: Context<QueueExpireEscrow>, computation_offset: u64) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.escrow_account.require_expirable(now)?;
+
+    // Lock Escrow & Global Mint until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .escrow_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let args = vec![
+        // Escrow Amount (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.escrow_account.amount_nonce),
+        Argument::Account(ctx.accounts.escrow_account.key(), 8 + 32, 32),
+        // Global Supply (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
+        Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ExpireEscrowCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.escrow_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.escrow_account.creator,
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("expire_escrow", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueExpireEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_EXPIRE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Escrow Account
+    #[account(
+        mut,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            escrow_account.creator.as_ref(),
+            &escrow_account.escrow_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+}
+
+#[event]
+pub struct EscrowExpiredEvent {
+    pub status: DCStatus,
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub new_global_mint_amount: [u8; 32],
+}
+
+// Callback Fn
+pub fn expire_escrow_callback(
+    ctx: Context<ExpireEscrowCallback>,
+    output: ComputationOutputs<ExpireEscrowOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(ExpireEscrowOutput {
+            field_0:
+                ExpireEscrowOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_global_mint_amount,
+                },
+        }) => (status_code, new_global_mint_amount),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(EscrowExpiredEvent {
+        status,
+        escrow: ctx.accounts.escrow_account.key(),
+        creator: ctx.accounts.escrow_account.creator,
+        new_global_mint_amount: o.1.ciphertexts[0],
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .escrow_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_global_mint_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    if !status.is_success() {
+        // Escrow stays active and can be expired again
+        ctx.accounts.escrow_account.lock.unlock();
+        ctx.accounts.dc_global_mint_account.lock.unlock();
+        return Ok(());
+    }
+
+    ctx.accounts.dc_global_mint_account.supply = o.1.ciphertexts[0];
+    ctx.accounts.dc_global_mint_account.supply_nonce = o.1.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
+
+    // Escrow is burned, return the rent to the creator
+    ctx.accounts
+        .escrow_account
+        .close(ctx.accounts.creator.to_account_info())?;
+
+    Ok(())
+}
+
+#[callback_accounts("expire_escrow")]
+#[derive(Accounts)]
+pub struct ExpireEscrowCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_EXPIRE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // Escrow Account
+    #[account(mut)]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+    #[account(mut)]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    /// CHECK: creator, receives the escrow rent once burned
+    #[account(
+        mut,
+        address = escrow_account.creator,
+    )]
+    pub creator: AccountInfo<'info>,
+}
@@ -0,0 +1,39 @@
+// Dead man's switch check-in, pushes the escrow deadline forward
+// Once the deadline passes without a heartbeat anyone can release to the beneficiary
+
+use crate::{DCEscrow, DC_ESCROW_SEED};
+use anchor_lang::prelude::*;
+
+pub fn heartbeat(ctx: Context<HeartbeatEscrow>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow_account;
+    escrow.heartbeat(now)?;
+
+    emit!(EscrowHeartbeatEvent {
+        escrow: escrow.key(),
+        unlock_at: escrow.unlock_at,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HeartbeatEscrow<'info> {
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            creator.key().as_ref(),
+            &escrow_account.escrow_id.to_le_bytes(),
+        ],
+        bump,
+        has_one = creator,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+}
+
+#[event]
+pub struct EscrowHeartbeatEvent {
+    pub escrow: Pubkey,
+    pub unlock_at: i64,
+}
@@ -0,0 +1,29 @@
+pub mod approve_escrow;
+pub use approve_escrow::*;
+
+pub mod close_unfunded_escrow;
+pub use close_unfunded_escrow::*;
+
+pub mod consts;
+pub use consts::*;
+
+pub mod claim_escrow;
+pub use claim_escrow::*;
+
+pub mod create_escrow;
+pub use create_escrow::*;
+
+pub mod expire_escrow;
+pub use expire_escrow::*;
+
+pub mod heartbeat;
+pub use heartbeat::*;
+
+pub mod release_escrow;
+pub use release_escrow::*;
+
+pub mod refund_escrow;
+pub use refund_escrow::*;
+
+pub mod state;
+pub use state::*;
@@ -0,0 +1,306 @@
+// Release an unlocked escrow to its beneficiary
+// Permissionless once unlocked & approved, the funds can only go to the beneficiary
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCEscrow, DC_ESCROW_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{DC_USER_TOKEN_ACCOUNT_SEED, RELEASE_ESCROW_CIRCUIT_HASH};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_RELEASE_ESCROW: u32 = comp_def_offset("release_escrow");
+
+// Init Comp Def
+pub fn init_release_escrow_comp_def(ctx: Context<InitReleaseEscrowCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("release_escrow");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: RELEASE_ESCROW_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("release_escrow", payer)]
+#[derive(Accounts)]
+pub struct InitReleaseEscrowCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_release_escrow(
+    ctx: Context<QueueReleaseEscrow>,
+    computation_offset: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.escrow_account.require_releasable(now)?;
+    if ctx.accounts.escrow_account.hash_lock.is_some() {
+        return Err(ErrorCode::EscrowHashLocked.into());
+    }
+
+    // Lock Escrow & Beneficiary Account until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .escrow_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .beneficiary_dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let args = vec![
+        // Escrow Amount (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.escrow_account.amount_nonce),
+        Argument::Account(ctx.accounts.escrow_account.key(), 8 + 32, 32),
+        // Beneficiary Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.beneficiary_dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.beneficiary_dc_user_token_account.amount_nonce),
+        Argument::Account(
+            ctx.accounts.beneficiary_dc_user_token_account.key(),
+            8 + 32 + 32,
+            32,
+        ),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ReleaseEscrowCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.escrow_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.beneficiary_dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.escrow_account.creator,
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("release_escrow", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueReleaseEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RELEASE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Escrow Account
+    #[account(
+        mut,
+        seeds = [
+            DC_ESCROW_SEED.as_bytes(),
+            escrow_account.creator.as_ref(),
+            &escrow_account.escrow_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+    // Beneficiary DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            escrow_account.beneficiary.as_ref(),
+            &DCUserTokenAccount::index_seed(beneficiary_dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub beneficiary_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+}
+
+#[event]
+pub struct EscrowReleasedEvent {
+    pub status: DCStatus,
+    pub escrow: Pubkey,
+    pub beneficiary: Pubkey,
+    pub new_beneficiary_balance: [u8; 32],
+}
+
+// Callback Fn
+pub fn release_escrow_callback(
+    ctx: Context<ReleaseEscrowCallback>,
+    output: ComputationOutputs<ReleaseEscrowOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(ReleaseEscrowOutput {
+            field_0:
+                ReleaseEscrowOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_beneficiary_balance,
+                },
+        }) => (status_code, new_beneficiary_balance),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    emit!(EscrowReleasedEvent {
+        status,
+        escrow: ctx.accounts.escrow_account.key(),
+        // Creator when the escrow was refunded
+        beneficiary: ctx.accounts.beneficiary_dc_user_token_account.owner,
+        new_beneficiary_balance: o.1.ciphertexts[0],
+    });
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .escrow_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .beneficiary_dc_user_token_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    if !status.is_success() {
+        // Escrow stays active and can be released again
+        ctx.accounts.escrow_account.lock.unlock();
+        ctx.accounts.beneficiary_dc_user_token_account.lock.unlock();
+        return Ok(());
+    }
+
+    ctx.accounts.beneficiary_dc_user_token_account.amount = o.1.ciphertexts[0];
+    ctx.accounts.beneficiary_dc_user_token_account.amount_nonce = o.1.nonce;
+    ctx.accounts.beneficiary_dc_user_token_account.lock.unlock();
+
+    // Escrow is empty, return the rent to the creator
+    ctx.accounts
+        .escrow_account
+        .close(ctx.accounts.creator.to_account_info())?;
+
+    Ok(())
+}
+
+#[callback_accounts("release_escrow")]
+#[derive(Accounts)]
+pub struct ReleaseEscrowCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_RELEASE_ESCROW)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // Escrow Account
+    #[account(mut)]
+    pub escrow_account: Box<Account<'info, DCEscrow>>,
+    // Beneficiary DC User Token Account (creator's on refund)
+    #[account(mut)]
+    pub beneficiary_dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    /// CHECK: creator, receives the escrow rent once released
+    #[account(
+        mut,
+        address = escrow_account.creator,
+    )]
+    pub creator: AccountInfo<'info>,
+}
@@ -0,0 +1,184 @@
+use crate::base::ErrorCode;
+use crate::{ComputationLock, MAX_ESCROW_APPROVERS};
+use anchor_lang::prelude::*;
+
+// DC locked up by a creator until it can be released to the beneficiary
+// amount must stay right after creator (read by the circuits at 8 + 32)
+#[account]
+#[derive(InitSpace)]
+pub struct DCEscrow {
+    pub creator: Pubkey,
+    pub amount: [u8; 32],
+    pub amount_nonce: u128,
+    pub lock: ComputationLock,
+    // Creator chosen id, lets one creator hold many escrows
+    pub escrow_id: u64,
+    // Wallet whose DCUserTokenAccount receives the escrow on release
+    pub beneficiary: Pubkey,
+    // Release is allowed from this timestamp on
+    pub unlock_at: i64,
+    pub status: EscrowStatus,
+    // Release must happen before this timestamp (0 = never expires)
+    pub expires_at: i64,
+    // M-of-N release, threshold = 0 means no approvals needed
+    #[max_len(MAX_ESCROW_APPROVERS)]
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    // Bit i set = approvers[i] approved
+    pub approvals: u16,
+    // Hash-lock mode, claimable by whoever knows the secret instead of released
+    pub hash_lock: Option<EscrowSecret>,
+    // Dead man's switch mode, each heartbeat pushes unlock_at to now + interval (0 = off)
+    pub heartbeat_interval: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum EscrowStatus {
+    // create_escrow computation in flight
+    Funding,
+    // Funded, waiting for release
+    Active,
+}
+
+// Enc<Shared, u128> secret committed by the creator, compared in MPC on claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct EscrowSecret {
+    pub pubkey: [u8; 32],
+    pub nonce: u128,
+    pub ciphertext: [u8; 32],
+}
+
+// Enc<Shared, { secret: u128, claimer: u128 }> under the claimer's owner_x25519, passed to
+// queue_claim_escrow. claimer is claimer_tag of the claiming wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowClaim {
+    pub nonce: u128,
+    pub secret: [u8; 32],
+    pub claimer: [u8; 32],
+}
+
+// Release conditions chosen by the creator, passed to queue_create_escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EscrowTerms {
+    pub beneficiary: Pubkey,
+    pub unlock_at: i64,
+    pub expires_at: i64,
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    // beneficiary is ignored for hash-locked escrows
+    pub hash_lock: Option<EscrowSecret>,
+    pub heartbeat_interval: i64,
+}
+
+impl EscrowTerms {
+    pub fn validate(&self) -> Result<()> {
+        if self.approvers.len() > MAX_ESCROW_APPROVERS
+            || self.threshold as usize > self.approvers.len()
+            || (self.expires_at != 0 && self.expires_at <= self.unlock_at)
+            || self.heartbeat_interval < 0
+            // A switch only ever pays out to its beneficiary
+            || (self.heartbeat_interval > 0 && (self.expires_at != 0 || self.hash_lock.is_some()))
+        {
+            return Err(ErrorCode::EscrowInvalid.into());
+        }
+        for (i, approver) in self.approvers.iter().enumerate() {
+            if self.approvers[..i].contains(approver) {
+                return Err(ErrorCode::EscrowInvalid.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DCEscrow {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+
+    pub fn approve(&mut self, approver: Pubkey) -> Result<()> {
+        let index = match self.approvers.iter().position(|a| *a == approver) {
+            Some(index) => index,
+            None => return Err(ErrorCode::EscrowNotApprover.into()),
+        };
+        if self.approvals & (1 << index) != 0 {
+            return Err(ErrorCode::EscrowAlreadyApproved.into());
+        }
+        self.approvals |= 1 << index;
+        Ok(())
+    }
+
+    pub fn require_releasable(&self, now: i64) -> Result<()> {
+        if self.status != EscrowStatus::Active {
+            return Err(ErrorCode::EscrowNotActive.into());
+        }
+        if now < self.unlock_at {
+            return Err(ErrorCode::EscrowLocked.into());
+        }
+        if self.is_expired(now) {
+            return Err(ErrorCode::EscrowExpired.into());
+        }
+        if self.approval_count() < self.threshold {
+            return Err(ErrorCode::EscrowThresholdNotMet.into());
+        }
+        Ok(())
+    }
+
+    pub fn heartbeat(&mut self, now: i64) -> Result<()> {
+        if self.status != EscrowStatus::Active {
+            return Err(ErrorCode::EscrowNotActive.into());
+        }
+        if self.heartbeat_interval == 0 {
+            return Err(ErrorCode::EscrowNoHeartbeat.into());
+        }
+        // Switch already fired, the beneficiary can release
+        if now >= self.unlock_at {
+            return Err(ErrorCode::EscrowDeadlinePassed.into());
+        }
+        self.unlock_at = match now.checked_add(self.heartbeat_interval) {
+            Some(unlock_at) => unlock_at,
+            None => return Err(ErrorCode::MathOverflow.into()),
+        };
+        Ok(())
+    }
+
+    pub fn require_expirable(&self, now: i64) -> Result<()> {
+        if self.status != EscrowStatus::Active {
+            return Err(ErrorCode::EscrowNotActive.into());
+        }
+        if !self.is_expired(now) {
+            return Err(ErrorCode::EscrowNotExpired.into());
+        }
+        Ok(())
+    }
+
+    // create_escrow callback never landed (aborted), the creator was never debited
+    pub fn require_unfunded(&self, now: i64) -> Result<()> {
+        if self.status != EscrowStatus::Funding {
+            return Err(ErrorCode::EscrowFunded.into());
+        }
+        if self.lock.is_locked(now) {
+            return Err(ErrorCode::ComputationInFlight.into());
+        }
+        Ok(())
+    }
+
+    // Approvals never reached the threshold, the creator can take the escrow back
+    // Only for escrows without an expiry, the others are left to the approvers until
+    // expires_at and then to expire_escrow
+    pub fn require_refundable(&self, now: i64) -> Result<()> {
+        if self.status != EscrowStatus::Active {
+            return Err(ErrorCode::EscrowNotActive.into());
+        }
+        if now < self.unlock_at {
+            return Err(ErrorCode::EscrowLocked.into());
+        }
+        if self.expires_at != 0 || self.threshold == 0 || self.approval_count() >= self.threshold {
+            return Err(ErrorCode::EscrowNotRefundable.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+// Permissionless crank for the daily lottery: closes the current round once its sales
+// window is over and opens the next one
+// Closing queues the round's draw on the draw_lottery circuit, rounds with too few
+// tickets are closed without one. The round before the closed one has had its claim
+// window lapse, so its unclaimed prizes roll over into the next round's pool
+
+// Queue Fn (callback is draw_lottery_callback)
+
+use crate::base::ErrorCode;
+use crate::{DCLottery, DCLotterySchedule, DrawLotteryCallback, LotteryStatus};
+use crate::{LotteryRoundOpenedEvent, SignerAccount};
+use crate::{DC_LOTTERY_ROUND_SEED, DC_LOTTERY_SCHEDULE_SEED, LOTTERY_WINNING_TICKETS};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::CallbackAccount;
+
+const COMP_DEF_OFFSET_DRAW_LOTTERY: u32 = comp_def_offset("draw_lottery");
+
+// Queue Fn
+pub fn queue_advance_lottery_round(
+    ctx: Context<QueueAdvanceLotteryRound>,
+    computation_offset: u64,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    if !ctx.accounts.round_account.is_closing_time(now) {
+        return Err(ErrorCode::LotteryRoundNotOver.into());
+    }
+
+    let round = ctx.accounts.lottery_schedule_account.current_round;
+    let rollover = match &ctx.accounts.previous_round_account {
+        Some(previous_round) => previous_round.unclaimed_prizes(now)?,
+        None if round == 0 => 0,
+        None => return Err(ErrorCode::LotteryInvalid.into()),
+    };
+
+    // The admin may already have drawn the round, otherwise it's closed here
+    let mut queue_draw = false;
+    if ctx.accounts.round_account.status == LotteryStatus::Open {
+        if (ctx.accounts.round_account.tickets_sold as usize) >= LOTTERY_WINNING_TICKETS {
+            // Lock Round until the draw lands, fails while a purchase is in flight
+            let computation_account = ctx.accounts.computation_account.key();
+            ctx.accounts
+                .round_account
+                .lock
+                .lock(computation_account, now)?;
+            ctx.accounts.round_account.status = LotteryStatus::Drawing;
+            queue_draw = true;
+        } else {
+            if ctx.accounts.round_account.lock.is_locked(now) {
+                return Err(ErrorCode::ComputationInFlight.into());
+            }
+            ctx.accounts.round_account.status = LotteryStatus::Closed;
+        }
+    }
+
+    emit!(LotteryRoundClosedEvent {
+        lottery: ctx.accounts.round_account.key(),
+        round,
+        status: ctx.accounts.round_account.status,
+        tickets_sold: ctx.accounts.round_account.tickets_sold,
+    });
+
+    let next_round = ctx
+        .accounts
+        .lottery_schedule_account
+        .open_round(round + 1, rollover, now)?;
+    ctx.accounts.next_round_account.set_inner(next_round);
+    ctx.accounts.lottery_schedule_account.current_round = round + 1;
+
+    emit!(LotteryRoundOpenedEvent {
+        lottery: ctx.accounts.next_round_account.key(),
+        round: round + 1,
+        closes_at: ctx.accounts.next_round_account.closes_at,
+        prize_pool: ctx.accounts.next_round_account.prize_pool,
+        rollover,
+    });
+
+    if queue_draw {
+        let args = vec![
+            // Tickets Sold (u16)
+            Argument::PlaintextU16(ctx.accounts.round_account.tickets_sold),
+        ];
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DrawLotteryCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.computation_account.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.round_account.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[queue_computation_accounts("draw_lottery", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueAdvanceLotteryRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DRAW_LOTTERY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [DC_LOTTERY_SCHEDULE_SEED.as_bytes()],
+        bump,
+    )]
+    pub lottery_schedule_account: Box<Account<'info, DCLotterySchedule>>,
+    // Round being closed
+    #[account(
+        mut,
+        seeds = [
+            DC_LOTTERY_ROUND_SEED.as_bytes(),
+            &lottery_schedule_account.current_round.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub round_account: Box<Account<'info, DCLottery>>,
+    // Round whose unclaimed prizes roll over, None while closing round 0
+    #[account(
+        seeds = [
+            DC_LOTTERY_ROUND_SEED.as_bytes(),
+            &lottery_schedule_account.current_round.wrapping_sub(1).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub previous_round_account: Option<Box<Account<'info, DCLottery>>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCLottery::INIT_SPACE,
+        seeds = [
+            DC_LOTTERY_ROUND_SEED.as_bytes(),
+            &(lottery_schedule_account.current_round + 1).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub next_round_account: Box<Account<'info, DCLottery>>,
+}
+
+#[event]
+pub struct LotteryRoundClosedEvent {
+    pub lottery: Pubkey,
+    pub round: u64,
+    // Drawing once the draw is queued, Closed if too few tickets were sold
+    pub status: LotteryStatus,
+    pub tickets_sold: u16,
+}
@@ -0,0 +1,394 @@
+// Buy a lottery ticket, the price is debited from the buyer and burned from the global supply
+// Bearer tickets don't record the buyer, whoever holds the x25519 key of the ownership
+// proof can claim them from any wallet. This is not a privacy feature, the buyer signs,
+// pays the rent and is debited here, so the purchase links the ticket to its buyer
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::BUY_TICKET_CIRCUIT_HASH;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCGlobalMint, DC_GLOBAL_MINT_SEED, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{DCLottery, DCLotteryTicket, TicketStatus, DC_LOTTERY_TICKET_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_BUY_TICKET: u32 = comp_def_offset("buy_ticket");
+
+// Init Comp Def
+pub fn init_buy_ticket_comp_def(ctx: Context<InitBuyTicketCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("buy_ticket");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: BUY_TICKET_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("buy_ticket", payer)]
+#[derive(Accounts)]
+pub struct InitBuyTicketCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_buy_ticket(
+    ctx: Context<QueueBuyTicket>,
+    computation_offset: u64,
+    bearer: bool,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.lottery_account.require_open(now)?;
+    // Purchases lock the lottery, so only one can be in flight and the sold out
+    // check can't be raced
+    if ctx.accounts.lottery_account.tickets_sold >= ctx.accounts.lottery_account.ticket_count {
+        return Err(ErrorCode::LotterySoldOut.into());
+    }
+
+    // Lock Global Mint, Buyer Account & Lottery until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .lottery_account
+        .lock
+        .lock(computation_account, now)?;
+
+    ctx.accounts.ticket_account.set_inner(DCLotteryTicket {
+        lottery: ctx.accounts.lottery_account.key(),
+        owner: if bearer {
+            Pubkey::default()
+        } else {
+            ctx.accounts.payer.key()
+        },
+        computation_offset,
+        ticket_number: 0,
+        status: TicketStatus::Pending,
+        owner_proof_nonce: 0,
+        owner_proof: [0; 32],
+    });
+
+    let args = vec![
+        // Global Supply (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
+        Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
+        // Buyer Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+        // Ticket Price (u64)
+        Argument::PlaintextU64(ctx.accounts.lottery_account.ticket_price),
+        // Ticket Tag (u64), encrypted for the buyer as the ownership proof
+        Argument::PlaintextU64(computation_offset),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![BuyTicketCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.lottery_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.payer.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("buy_ticket", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueBuyTicket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_BUY_TICKET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // Buyer DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            lottery_account.seed(),
+            &lottery_account.lottery_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DCLotteryTicket::INIT_SPACE,
+        seeds = [
+            DC_LOTTERY_TICKET_SEED.as_bytes(),
+            lottery_account.key().as_ref(),
+            &computation_offset.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub ticket_account: Box<Account<'info, DCLotteryTicket>>,
+}
+
+#[event]
+pub struct TicketPurchasedEvent {
+    pub status: DCStatus,
+    pub lottery: Pubkey,
+    pub ticket: Pubkey,
+    pub owner: Pubkey,
+    pub ticket_number: u16,
+    pub new_global_mint_amount: [u8; 32],
+    pub new_buyer_balance: [u8; 32],
+}
+
+// Callback Fn
+pub fn buy_ticket_callback(
+    ctx: Context<BuyTicketCallback>,
+    output: ComputationOutputs<BuyTicketOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(BuyTicketOutput {
+            field_0:
+                BuyTicketOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_global_mint_amount,
+                    field_2: new_buyer_balance,
+                    field_3: owner_proof,
+                },
+        }) => (
+            status_code,
+            new_global_mint_amount,
+            new_buyer_balance,
+            owner_proof,
+        ),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+        && ctx
+            .accounts
+            .lottery_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+    }
+
+    if !status.is_success() || !is_pending {
+        if is_pending {
+            ctx.accounts.dc_global_mint_account.lock.unlock();
+            ctx.accounts.dc_user_token_account.lock.unlock();
+            ctx.accounts.lottery_account.lock.unlock();
+        }
+
+        emit!(TicketPurchasedEvent {
+            status,
+            lottery: ctx.accounts.lottery_account.key(),
+            ticket: ctx.accounts.ticket_account.key(),
+            owner: ctx.accounts.ticket_account.owner,
+            ticket_number: 0,
+            new_global_mint_amount: o.1.ciphertexts[0],
+            new_buyer_balance: o.2.ciphertexts[0],
+        });
+
+        // Never paid for, return the rent to the buyer
+        ctx.accounts
+            .ticket_account
+            .close(ctx.accounts.buyer.to_account_info())?;
+        return Ok(());
+    }
+
+    ctx.accounts.dc_global_mint_account.supply = o.1.ciphertexts[0];
+    ctx.accounts.dc_global_mint_account.supply_nonce = o.1.nonce;
+    ctx.accounts.dc_global_mint_account.lock.unlock();
+    ctx.accounts.dc_user_token_account.amount = o.2.ciphertexts[0];
+    ctx.accounts.dc_user_token_account.amount_nonce = o.2.nonce;
+    ctx.accounts.dc_user_token_account.lock.unlock();
+
+    let ticket_number = ctx.accounts.lottery_account.tickets_sold;
+    ctx.accounts.lottery_account.tickets_sold += 1;
+    ctx.accounts.lottery_account.lock.unlock();
+    ctx.accounts.ticket_account.ticket_number = ticket_number;
+    ctx.accounts.ticket_account.status = TicketStatus::Active;
+    ctx.accounts.ticket_account.owner_proof = o.3.ciphertexts[0];
+    ctx.accounts.ticket_account.owner_proof_nonce = o.3.nonce;
+
+    emit!(TicketPurchasedEvent {
+        status,
+        lottery: ctx.accounts.lottery_account.key(),
+        ticket: ctx.accounts.ticket_account.key(),
+        owner: ctx.accounts.ticket_account.owner,
+        ticket_number,
+        new_global_mint_amount: o.1.ciphertexts[0],
+        new_buyer_balance: o.2.ciphertexts[0],
+    });
+
+    Ok(())
+}
+
+#[callback_accounts("buy_ticket")]
+#[derive(Accounts)]
+pub struct BuyTicketCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_BUY_TICKET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // Buyer DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    #[account(mut)]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+    #[account(mut)]
+    pub ticket_account: Box<Account<'info, DCLotteryTicket>>,
+    /// CHECK: buyer, set by queue_buy_ticket (bearer tickets don't record it), receives the
+    /// ticket rent if the purchase fails
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+}
@@ -0,0 +1,413 @@
+// Claim the jackpot with every winning ticket of a lottery, the full prize pool is minted to the owner
+// Ownership of every ticket is proven in MPC with one TicketClaim made out to the signer for
+// the first winning ticket, so bearer tickets can be claimed too. Every ticket must be held
+// under the same key
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{claimer_tag, DCLottery, DCLotteryTicket, TicketClaim, TicketStatus};
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCGlobalMint, DC_GLOBAL_MINT_SEED, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{CLAIM_JACKPOT_CIRCUIT_HASH, LOTTERY_WINNING_TICKETS};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_CLAIM_JACKPOT: u32 = comp_def_offset("claim_jackpot");
+
+// Init Comp Def
+pub fn init_claim_jackpot_comp_def(ctx: Context<InitClaimJackpotCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("claim_jackpot");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: CLAIM_JACKPOT_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("claim_jackpot", payer)]
+#[derive(Accounts)]
+pub struct InitClaimJackpotCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_claim_jackpot(
+    ctx: Context<QueueClaimJackpot>,
+    computation_offset: u64,
+    claim: TicketClaim,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.lottery_account.require_claimable(now)?;
+    // Tickets are passed in draw order, so every winning ticket is covered exactly once
+    let lottery = ctx.accounts.lottery_account.key();
+    let owner = ctx.accounts.payer.key();
+    for (ticket, winning_ticket) in ctx
+        .accounts
+        .tickets()
+        .into_iter()
+        .zip(ctx.accounts.lottery_account.winning_tickets)
+    {
+        ticket.require_redeemable(&lottery, &owner)?;
+        if ticket.ticket_number != winning_ticket {
+            return Err(ErrorCode::LotteryTicketNotWinning.into());
+        }
+    }
+
+    // Lock Global Mint, Claimer Account & Lottery until the callback lands
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .dc_global_mint_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .dc_user_token_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts
+        .lottery_account
+        .lock
+        .lock(computation_account, now)?;
+
+    let mut args = vec![
+        // Global Supply (Enc<Mxe, u64>)
+        Argument::PlaintextU128(ctx.accounts.dc_global_mint_account.supply_nonce),
+        Argument::Account(ctx.accounts.dc_global_mint_account.key(), 8 + 32, 32),
+        // Claimer Balance (Enc<Shared, u64>)
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(ctx.accounts.dc_user_token_account.amount_nonce),
+        Argument::Account(ctx.accounts.dc_user_token_account.key(), 8 + 32 + 32, 32),
+    ];
+    let tickets = ctx.accounts.tickets();
+    // Ticket Owner Proofs (Enc<Shared, u64>), decrypted with the claim's key
+    for ticket in tickets {
+        args.push(Argument::ArcisPubkey(claim.pubkey));
+        args.push(Argument::PlaintextU128(ticket.owner_proof_nonce));
+        args.push(Argument::EncryptedU64(ticket.owner_proof));
+    }
+    args.extend([
+        // Ticket Claim (Enc<Shared, TicketClaim>)
+        Argument::ArcisPubkey(claim.pubkey),
+        Argument::PlaintextU128(claim.nonce),
+        Argument::EncryptedU64(claim.ticket_tag),
+        Argument::EncryptedU128(claim.claimer),
+    ]);
+    // Ticket Tags ([u64; 5])
+    for ticket in tickets {
+        args.push(Argument::PlaintextU64(ticket.computation_offset));
+    }
+    args.extend([
+        // Claimer (u128)
+        Argument::PlaintextU128(claimer_tag(&ctx.accounts.payer.key())),
+        // Prize Pool (u64)
+        Argument::PlaintextU64(ctx.accounts.lottery_account.prize_pool),
+    ]);
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![ClaimJackpotCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_global_mint_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.lottery_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account_0.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account_1.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account_2.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account_3.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account_4.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("claim_jackpot", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueClaimJackpot<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_JACKPOT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [DC_GLOBAL_MINT_SEED.as_bytes()],
+        bump,
+    )]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // Claimer DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            lottery_account.seed(),
+            &lottery_account.lottery_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+    pub ticket_account_0: Box<Account<'info, DCLotteryTicket>>,
+    pub ticket_account_1: Box<Account<'info, DCLotteryTicket>>,
+    pub ticket_account_2: Box<Account<'info, DCLotteryTicket>>,
+    pub ticket_account_3: Box<Account<'info, DCLotteryTicket>>,
+    pub ticket_account_4: Box<Account<'info, DCLotteryTicket>>,
+}
+
+impl QueueClaimJackpot<'_> {
+    fn tickets(&self) -> [&DCLotteryTicket; LOTTERY_WINNING_TICKETS] {
+        [
+            &self.ticket_account_0,
+            &self.ticket_account_1,
+            &self.ticket_account_2,
+            &self.ticket_account_3,
+            &self.ticket_account_4,
+        ]
+    }
+}
+
+#[event]
+pub struct JackpotClaimedEvent {
+    pub status: DCStatus,
+    pub lottery: Pubkey,
+    pub owner: Pubkey,
+    pub prize: u64,
+    pub new_global_mint_amount: [u8; 32],
+    pub new_claimer_balance: [u8; 32],
+}
+
+// Callback Fn
+pub fn claim_jackpot_callback(
+    ctx: Context<ClaimJackpotCallback>,
+    output: ComputationOutputs<ClaimJackpotOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(ClaimJackpotOutput {
+            field_0:
+                ClaimJackpotOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_global_mint_amount,
+                    field_2: new_claimer_balance,
+                },
+        }) => (status_code, new_global_mint_amount, new_claimer_balance),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    let computation_account = ctx.accounts.computation_account.key();
+    let is_pending = ctx
+        .accounts
+        .dc_global_mint_account
+        .lock
+        .is_pending(computation_account)
+        && ctx
+            .accounts
+            .dc_user_token_account
+            .lock
+            .is_pending(computation_account)
+        && ctx
+            .accounts
+            .lottery_account
+            .lock
+            .is_pending(computation_account);
+
+    if !is_pending {
+        // Accounts moved on since queue (lock timed out), don't touch them
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    if status.is_success() {
+        ctx.accounts.dc_global_mint_account.supply = o.1.ciphertexts[0];
+        ctx.accounts.dc_global_mint_account.supply_nonce = o.1.nonce;
+        ctx.accounts.dc_global_mint_account.lock.unlock();
+        ctx.accounts.dc_user_token_account.amount = o.2.ciphertexts[0];
+        ctx.accounts.dc_user_token_account.amount_nonce = o.2.nonce;
+        ctx.accounts.dc_user_token_account.lock.unlock();
+
+        let prize = ctx.accounts.lottery_account.prize_pool;
+        ctx.accounts.lottery_account.prizes_claimed = ctx
+            .accounts
+            .lottery_account
+            .prizes_claimed
+            .saturating_add(prize);
+        for ticket in [
+            &mut ctx.accounts.ticket_account_0,
+            &mut ctx.accounts.ticket_account_1,
+            &mut ctx.accounts.ticket_account_2,
+            &mut ctx.accounts.ticket_account_3,
+            &mut ctx.accounts.ticket_account_4,
+        ] {
+            ticket.status = TicketStatus::Redeemed;
+        }
+    } else {
+        ctx.accounts.dc_global_mint_account.lock.unlock();
+        ctx.accounts.dc_user_token_account.lock.unlock();
+    }
+    ctx.accounts.lottery_account.lock.unlock();
+
+    emit!(JackpotClaimedEvent {
+        status,
+        lottery: ctx.accounts.lottery_account.key(),
+        owner: ctx.accounts.dc_user_token_account.owner,
+        prize: ctx.accounts.lottery_account.prize_pool,
+        new_global_mint_amount: o.1.ciphertexts[0],
+        new_claimer_balance: o.2.ciphertexts[0],
+    });
+
+    Ok(())
+}
+
+#[callback_accounts("claim_jackpot")]
+#[derive(Accounts)]
+pub struct ClaimJackpotCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CLAIM_JACKPOT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub dc_global_mint_account: Account<'info, DCGlobalMint>,
+    // Claimer DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    #[account(mut)]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+    #[account(mut)]
+    pub ticket_account_0: Box<Account<'info, DCLotteryTicket>>,
+    #[account(mut)]
+    pub ticket_account_1: Box<Account<'info, DCLotteryTicket>>,
+    #[account(mut)]
+    pub ticket_account_2: Box<Account<'info, DCLotteryTicket>>,
+    #[account(mut)]
+    pub ticket_account_3: Box<Account<'info, DCLotteryTicket>>,
+    #[account(mut)]
+    pub ticket_account_4: Box<Account<'info, DCLotteryTicket>>,
+}
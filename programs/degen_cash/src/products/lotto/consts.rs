@@ -0,0 +1,6 @@
+pub const DC_LOTTERY_SEED: &str = "dc_lottery";
+pub const DC_LOTTERY_TICKET_SEED: &str = "dc_lottery_ticket";
+pub const DC_LOTTERY_SCHEDULE_SEED: &str = "dc_lottery_schedule";
+pub const DC_LOTTERY_ROUND_SEED: &str = "dc_lottery_round";
+// Tickets picked per draw, mirrored by WINNING_TICKETS in the draw_lottery circuit
+pub const LOTTERY_WINNING_TICKETS: usize = 5;
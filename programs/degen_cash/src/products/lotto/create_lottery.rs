@@ -0,0 +1,67 @@
+// Admin creates a lottery, no Arcium compute needed
+
+use crate::{DCConfig, DCLottery, DC_CONFIG_SEED, DC_LOTTERY_SEED};
+use anchor_lang::prelude::*;
+
+pub fn create_lottery(
+    ctx: Context<CreateLottery>,
+    lottery_id: u64,
+    ticket_price: u64,
+    ticket_count: u16,
+    prize_pool: u64,
+    ticket_prize: u64,
+    claim_window: i64,
+) -> Result<()> {
+    ctx.accounts.lottery_account.set_inner(DCLottery::new(
+        lottery_id,
+        ticket_price,
+        ticket_count,
+        prize_pool,
+        ticket_prize,
+        claim_window,
+    )?);
+
+    emit!(LotteryCreatedEvent {
+        lottery: ctx.accounts.lottery_account.key(),
+        lottery_id,
+        ticket_price,
+        ticket_count,
+        prize_pool,
+        ticket_prize,
+        claim_window,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct CreateLottery<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+        has_one = admin,
+    )]
+    pub dc_config_account: Account<'info, DCConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DCLottery::INIT_SPACE,
+        seeds = [DC_LOTTERY_SEED.as_bytes(), &lottery_id.to_le_bytes()],
+        bump,
+    )]
+    pub lottery_account: Account<'info, DCLottery>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct LotteryCreatedEvent {
+    pub lottery: Pubkey,
+    pub lottery_id: u64,
+    pub ticket_price: u64,
+    pub ticket_count: u16,
+    pub prize_pool: u64,
+    pub ticket_prize: u64,
+    pub claim_window: i64,
+}
@@ -0,0 +1,88 @@
+// Admin sets up the daily lottery and opens its first round, no Arcium compute needed
+// Rounds after that are opened by the permissionless advance_lottery_round crank
+
+use crate::base::ErrorCode;
+use crate::{DCConfig, DCLottery, DCLotterySchedule, DC_CONFIG_SEED};
+use crate::{DC_LOTTERY_ROUND_SEED, DC_LOTTERY_SCHEDULE_SEED};
+use anchor_lang::prelude::*;
+
+pub fn create_lottery_schedule(
+    ctx: Context<CreateLotterySchedule>,
+    ticket_price: u64,
+    ticket_count: u16,
+    prize_pool: u64,
+    ticket_prize: u64,
+    claim_window: i64,
+    round_duration: i64,
+) -> Result<()> {
+    // A round's claims must lapse before its pool rolls over, see DCLotterySchedule
+    if round_duration <= claim_window {
+        return Err(ErrorCode::LotteryInvalid.into());
+    }
+
+    ctx.accounts
+        .lottery_schedule_account
+        .set_inner(DCLotterySchedule {
+            ticket_price,
+            ticket_count,
+            prize_pool,
+            ticket_prize,
+            claim_window,
+            round_duration,
+            current_round: 0,
+        });
+
+    let now = Clock::get()?.unix_timestamp;
+    let round = ctx
+        .accounts
+        .lottery_schedule_account
+        .open_round(0, 0, now)?;
+    ctx.accounts.round_account.set_inner(round);
+
+    emit!(LotteryRoundOpenedEvent {
+        lottery: ctx.accounts.round_account.key(),
+        round: 0,
+        closes_at: ctx.accounts.round_account.closes_at,
+        prize_pool: ctx.accounts.round_account.prize_pool,
+        rollover: 0,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateLotterySchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+        has_one = admin,
+    )]
+    pub dc_config_account: Account<'info, DCConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DCLotterySchedule::INIT_SPACE,
+        seeds = [DC_LOTTERY_SCHEDULE_SEED.as_bytes()],
+        bump,
+    )]
+    pub lottery_schedule_account: Account<'info, DCLotterySchedule>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DCLottery::INIT_SPACE,
+        seeds = [DC_LOTTERY_ROUND_SEED.as_bytes(), &0u64.to_le_bytes()],
+        bump,
+    )]
+    pub round_account: Account<'info, DCLottery>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct LotteryRoundOpenedEvent {
+    pub lottery: Pubkey,
+    pub round: u64,
+    pub closes_at: i64,
+    pub prize_pool: u64,
+    pub rollover: u64,
+}
@@ -0,0 +1,272 @@
+// Draw the winning tickets of a lottery with ArcisRNG, revealed in the callback
+// Admin lotteries are drawn by the admin, daily rounds by anyone once their sales window
+// is over, so a round left Drawing by a failed or aborted draw can always be drawn again
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCConfig, DCLottery, LotteryStatus, DC_CONFIG_SEED};
+use crate::{DCStatus, SignerAccount, StaleComputationEvent};
+use crate::{DRAW_LOTTERY_CIRCUIT_HASH, LOTTERY_WINNING_TICKETS};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_DRAW_LOTTERY: u32 = comp_def_offset("draw_lottery");
+
+// Init Comp Def
+pub fn init_draw_lottery_comp_def(ctx: Context<InitDrawLotteryCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("draw_lottery");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: DRAW_LOTTERY_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("draw_lottery", payer)]
+#[derive(Accounts)]
+pub struct InitDrawLotteryCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_draw_lottery(ctx: Context<QueueDrawLottery>, computation_offset: u64) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    // Drawing is allowed again if a previous draw failed, or aborted once its lock timed out
+    if ctx.accounts.lottery_account.status == LotteryStatus::Drawn {
+        return Err(ErrorCode::LotteryAlreadyDrawn.into());
+    }
+    if (ctx.accounts.lottery_account.tickets_sold as usize) < LOTTERY_WINNING_TICKETS {
+        return Err(ErrorCode::LotteryNotEnoughTickets.into());
+    }
+    // Rounds can only be drawn once their sales window is over
+    let now = Clock::get()?.unix_timestamp;
+    if ctx.accounts.lottery_account.is_round && !ctx.accounts.lottery_account.is_closing_time(now) {
+        return Err(ErrorCode::LotteryRoundNotOver.into());
+    }
+
+    // Lock Lottery until the callback lands, fails while a purchase is in flight
+    let computation_account = ctx.accounts.computation_account.key();
+    ctx.accounts
+        .lottery_account
+        .lock
+        .lock(computation_account, now)?;
+    ctx.accounts.lottery_account.status = LotteryStatus::Drawing;
+
+    let args = vec![
+        // Tickets Sold (u16)
+        Argument::PlaintextU16(ctx.accounts.lottery_account.tickets_sold),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![DrawLotteryCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.lottery_account.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("draw_lottery", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueDrawLottery<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DRAW_LOTTERY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [
+            lottery_account.seed(),
+            &lottery_account.lottery_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+    // Only needed for admin lotteries, anyone can draw a round once its sales window is over
+    pub admin: Option<Signer<'info>>,
+    #[account(
+        seeds = [DC_CONFIG_SEED.as_bytes()],
+        bump,
+        constraint = lottery_account.is_round
+            || admin.as_ref().map(|a| a.key()) == Some(dc_config_account.admin)
+            @ ErrorCode::NotAdmin,
+    )]
+    pub dc_config_account: Box<Account<'info, DCConfig>>,
+}
+
+#[event]
+pub struct LotteryDrawnEvent {
+    pub status: DCStatus,
+    pub lottery: Pubkey,
+    pub winning_tickets: [u16; LOTTERY_WINNING_TICKETS],
+    pub drawn_at: i64,
+}
+
+// Callback Fn
+pub fn draw_lottery_callback(
+    ctx: Context<DrawLotteryCallback>,
+    output: ComputationOutputs<DrawLotteryOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(DrawLotteryOutput {
+            field_0:
+                DrawLotteryOutputStruct0 {
+                    field_0: status_code,
+                    field_1: ticket_0,
+                    field_2: ticket_1,
+                    field_3: ticket_2,
+                    field_4: ticket_3,
+                    field_5: ticket_4,
+                },
+        }) => (
+            status_code,
+            [ticket_0, ticket_1, ticket_2, ticket_3, ticket_4],
+        ),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .lottery_account
+        .lock
+        .is_pending(computation_account)
+    {
+        // Lottery moved on since queue (lock timed out), don't touch it
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    ctx.accounts.lottery_account.lock.unlock();
+    let now = Clock::get()?.unix_timestamp;
+
+    if status.is_success() {
+        ctx.accounts.lottery_account.winning_tickets = o.1;
+        ctx.accounts.lottery_account.drawn_at = now;
+        ctx.accounts.lottery_account.status = LotteryStatus::Drawn;
+    }
+    // On failure the lottery stays Drawing and the draw can be queued again
+
+    emit!(LotteryDrawnEvent {
+        status,
+        lottery: ctx.accounts.lottery_account.key(),
+        winning_tickets: ctx.accounts.lottery_account.winning_tickets,
+        drawn_at: ctx.accounts.lottery_account.drawn_at,
+    });
+
+    Ok(())
+}
+
+#[callback_accounts("draw_lottery")]
+#[derive(Accounts)]
+pub struct DrawLotteryCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DRAW_LOTTERY)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub lottery_account: Box<Account<'info, DCLottery>>,
+}
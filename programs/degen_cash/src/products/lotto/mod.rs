@@ -0,0 +1,29 @@
+pub mod consts;
+pub use consts::*;
+
+pub mod create_lottery;
+pub use create_lottery::*;
+
+pub mod buy_ticket;
+pub use buy_ticket::*;
+
+pub mod draw_lottery;
+pub use draw_lottery::*;
+
+pub mod claim_jackpot;
+pub use claim_jackpot::*;
+
+pub mod redeem_ticket;
+pub use redeem_ticket::*;
+
+pub mod rekey_ticket;
+pub use rekey_ticket::*;
+
+pub mod create_lottery_schedule;
+pub use create_lottery_schedule::*;
+
+pub mod advance_lottery_round;
+pub use advance_lottery_round::*;
+
+pub mod state;
+pub use state::*;
@@ -0,0 +1,267 @@
+// Move a ticket's ownership proof to the owner's current key after queue_rotate_dc_key
+// Proofs are encrypted under the key used at purchase, so without this tickets bought
+// before a rotation could no longer be claimed. The old key is proven with a TicketClaim
+// like a redemption, so tickets held under any earlier key can be moved
+
+// Init Comp Def
+// Queue Fn
+// Callback Fn
+
+use crate::base::ErrorCode;
+use crate::REKEY_TICKET_CIRCUIT_HASH;
+use crate::{claimer_tag, DCLotteryTicket, TicketClaim, DC_USER_TOKEN_ACCOUNT_SEED};
+use crate::{DCCircuitConfig, DC_CIRCUIT_CONFIG_SEED};
+use crate::{DCStatus, DCUserTokenAccount, SignerAccount, StaleComputationEvent};
+use crate::{ID, ID_CONST};
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use arcium_client::idl::arcium::types::{CallbackAccount, CircuitSource, OffChainCircuitSource};
+
+const COMP_DEF_OFFSET_REKEY_TICKET: u32 = comp_def_offset("rekey_ticket");
+
+// Init Comp Def
+pub fn init_rekey_ticket_comp_def(ctx: Context<InitRekeyTicketCompDef>) -> Result<()> {
+    let source = ctx
+        .accounts
+        .circuit_config_account
+        .circuit_source("rekey_ticket");
+    init_comp_def(
+        ctx.accounts,
+        true,
+        0,
+        Some(CircuitSource::OffChain(OffChainCircuitSource {
+            source,
+            hash: REKEY_TICKET_CIRCUIT_HASH,
+        })),
+        None,
+    )?;
+    Ok(())
+}
+
+#[init_computation_definition_accounts("rekey_ticket", payer)]
+#[derive(Accounts)]
+pub struct InitRekeyTicketCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        seeds = [DC_CIRCUIT_CONFIG_SEED.as_bytes()],
+        bump,
+    )]
+    pub circuit_config_account: Box<Account<'info, DCCircuitConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Queue Fn
+pub fn queue_rekey_ticket(
+    ctx: Context<QueueRekeyTicket>,
+    computation_offset: u64,
+    claim: TicketClaim,
+    nonce: u128,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    ctx.accounts
+        .ticket_account
+        .require_rekeyable(&ctx.accounts.payer.key())?;
+
+    // Lock Owner Account until the callback lands, so the key can't rotate again meanwhile
+    ctx.accounts.dc_user_token_account.lock.lock(
+        ctx.accounts.computation_account.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let args = vec![
+        // Ticket Owner Proof (Enc<Shared, u64>), decrypted with the claim's (old) key
+        Argument::ArcisPubkey(claim.pubkey),
+        Argument::PlaintextU128(ctx.accounts.ticket_account.owner_proof_nonce),
+        Argument::EncryptedU64(ctx.accounts.ticket_account.owner_proof),
+        // Ticket Claim (Enc<Shared, TicketClaim>)
+        Argument::ArcisPubkey(claim.pubkey),
+        Argument::PlaintextU128(claim.nonce),
+        Argument::EncryptedU64(claim.ticket_tag),
+        Argument::EncryptedU128(claim.claimer),
+        // New Owner (Enc<Shared, {}>), the owner's current key
+        Argument::ArcisPubkey(ctx.accounts.dc_user_token_account.owner_x25519),
+        Argument::PlaintextU128(nonce),
+        // Ticket Tag (u64)
+        Argument::PlaintextU64(ctx.accounts.ticket_account.computation_offset),
+        // Claimer (u128)
+        Argument::PlaintextU128(claimer_tag(&ctx.accounts.payer.key())),
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![RekeyTicketCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.computation_account.key(),
+                is_writable: false,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.dc_user_token_account.key(),
+                is_writable: true,
+            },
+            CallbackAccount {
+                pubkey: ctx.accounts.ticket_account.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    Ok(())
+}
+
+#[queue_computation_accounts("rekey_ticket", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct QueueRekeyTicket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REKEY_TICKET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    // Owner DC User Token Account
+    #[account(
+        mut,
+        seeds = [
+            DC_USER_TOKEN_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+            &DCUserTokenAccount::index_seed(dc_user_token_account.index),
+        ],
+        bump,
+    )]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    pub ticket_account: Box<Account<'info, DCLotteryTicket>>,
+}
+
+#[event]
+pub struct TicketRekeyedEvent {
+    pub status: DCStatus,
+    pub ticket: Pubkey,
+}
+
+// Callback Fn
+pub fn rekey_ticket_callback(
+    ctx: Context<RekeyTicketCallback>,
+    output: ComputationOutputs<RekeyTicketOutput>,
+) -> Result<()> {
+    let o = match output {
+        ComputationOutputs::Success(RekeyTicketOutput {
+            field_0:
+                RekeyTicketOutputStruct0 {
+                    field_0: status_code,
+                    field_1: new_owner_proof,
+                },
+        }) => (status_code, new_owner_proof),
+        _ => return Err(ErrorCode::AbortedComputation.into()),
+    };
+    let status = DCStatus::from_code(o.0);
+
+    let computation_account = ctx.accounts.computation_account.key();
+    if !ctx
+        .accounts
+        .dc_user_token_account
+        .lock
+        .is_pending(computation_account)
+    {
+        // Account moved on since queue (lock timed out), don't touch it
+        emit!(StaleComputationEvent {
+            computation_account
+        });
+        return Ok(());
+    }
+
+    // NotOwner leaves the proof as is
+    if status.is_success() {
+        ctx.accounts.ticket_account.owner_proof = o.1.ciphertexts[0];
+        ctx.accounts.ticket_account.owner_proof_nonce = o.1.nonce;
+    }
+    ctx.accounts.dc_user_token_account.lock.unlock();
+
+    emit!(TicketRekeyedEvent {
+        status,
+        ticket: ctx.accounts.ticket_account.key(),
+    });
+
+    Ok(())
+}
+
+#[callback_accounts("rekey_ticket")]
+#[derive(Accounts)]
+pub struct RekeyTicketCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REKEY_TICKET)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    /// CHECK: computation_account, only compared against the pending computation lock
+    pub computation_account: AccountInfo<'info>,
+    // Owner DC User Token Account
+    #[account(mut)]
+    pub dc_user_token_account: Account<'info, DCUserTokenAccount>,
+    #[account(mut)]
+    pub ticket_account: Box<Account<'info, DCLotteryTicket>>,
+}
@@ -0,0 +1,241 @@
+use crate::base::ErrorCode;
+use crate::{ComputationLock, DC_LOTTERY_ROUND_SEED, DC_LOTTERY_SEED, LOTTERY_WINNING_TICKETS};
+use anchor_lang::prelude::*;
+
+// Lottery created by the DC admin, or a round of the daily schedule
+// Ticket sales are burned from the global supply, prizes are minted when claimed
+#[account]
+#[derive(InitSpace)]
+pub struct DCLottery {
+    pub lottery_id: u64,
+    pub ticket_price: u64,
+    pub ticket_count: u16,
+    // Tickets are numbered 0..tickets_sold in purchase order
+    pub tickets_sold: u16,
+    pub prize_pool: u64,
+    pub status: LotteryStatus,
+    // Held by ticket purchases and the draw, so tickets_sold can't change mid draw
+    pub lock: ComputationLock,
+    // Valid once status is Drawn
+    pub winning_tickets: [u16; LOTTERY_WINNING_TICKETS],
+    pub drawn_at: i64,
+    // Fixed amount minted for each single winning ticket
+    pub ticket_prize: u64,
+    // Seconds after the draw during which prizes can be claimed, unclaimed prizes lapse after
+    pub claim_window: i64,
+    // Total minted to winners so far
+    pub prizes_claimed: u64,
+    // Round of the daily schedule, keyed by round index (lottery_id) instead of the admin's id
+    pub is_round: bool,
+    // End of ticket sales for rounds, 0 for admin lotteries which close when drawn
+    pub closes_at: i64,
+    // Unclaimed prizes of an earlier round included in prize_pool
+    pub rollover: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum LotteryStatus {
+    // Selling tickets
+    Open,
+    // Draw queued, sales are closed
+    Drawing,
+    // Winning tickets revealed
+    Drawn,
+    // Round closed with too few tickets to draw, the whole pool rolls over
+    Closed,
+}
+
+impl DCLottery {
+    // Open lottery with validated terms
+    pub fn new(
+        lottery_id: u64,
+        ticket_price: u64,
+        ticket_count: u16,
+        prize_pool: u64,
+        ticket_prize: u64,
+        claim_window: i64,
+    ) -> Result<Self> {
+        if ticket_price == 0
+            || (ticket_count as usize) < LOTTERY_WINNING_TICKETS
+            || prize_pool == 0
+            || ticket_prize == 0
+            || claim_window <= 0
+        {
+            return Err(ErrorCode::LotteryInvalid.into());
+        }
+
+        Ok(Self {
+            lottery_id,
+            ticket_price,
+            ticket_count,
+            tickets_sold: 0,
+            prize_pool,
+            status: LotteryStatus::Open,
+            lock: Default::default(),
+            winning_tickets: [0; LOTTERY_WINNING_TICKETS],
+            drawn_at: 0,
+            ticket_prize,
+            claim_window,
+            prizes_claimed: 0,
+            is_round: false,
+            closes_at: 0,
+            rollover: 0,
+        })
+    }
+
+    // Rounds and admin lotteries live under different seeds so their ids never collide
+    pub fn seed(&self) -> &'static [u8] {
+        if self.is_round {
+            DC_LOTTERY_ROUND_SEED.as_bytes()
+        } else {
+            DC_LOTTERY_SEED.as_bytes()
+        }
+    }
+
+    pub fn is_closing_time(&self, now: i64) -> bool {
+        self.is_round && now >= self.closes_at
+    }
+
+    pub fn require_open(&self, now: i64) -> Result<()> {
+        if self.status != LotteryStatus::Open || self.is_closing_time(now) {
+            return Err(ErrorCode::LotteryNotOpen.into());
+        }
+        Ok(())
+    }
+
+    pub fn claim_deadline(&self) -> Result<i64> {
+        self.drawn_at
+            .checked_add(self.claim_window)
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    pub fn require_claimable(&self, now: i64) -> Result<()> {
+        if self.status != LotteryStatus::Drawn {
+            return Err(ErrorCode::LotteryNotDrawn.into());
+        }
+        if now > self.claim_deadline()? {
+            return Err(ErrorCode::LotteryClaimExpired.into());
+        }
+        Ok(())
+    }
+
+    pub fn is_winning_ticket(&self, ticket_number: u16) -> bool {
+        self.winning_tickets.contains(&ticket_number)
+    }
+
+    // Prize pool left over once no more claims can land
+    pub fn unclaimed_prizes(&self, now: i64) -> Result<u64> {
+        if self.lock.is_locked(now) {
+            return Err(ErrorCode::ComputationInFlight.into());
+        }
+        match self.status {
+            LotteryStatus::Closed => Ok(self.prize_pool),
+            LotteryStatus::Drawn if now > self.claim_deadline()? => {
+                Ok(self.prize_pool.saturating_sub(self.prizes_claimed))
+            }
+            LotteryStatus::Drawn => Err(ErrorCode::LotteryClaimWindowOpen.into()),
+            LotteryStatus::Open | LotteryStatus::Drawing => Err(ErrorCode::LotteryNotDrawn.into()),
+        }
+    }
+}
+
+// Terms of the daily lottery, every round is opened with them by the permissionless crank
+#[account]
+#[derive(InitSpace)]
+pub struct DCLotterySchedule {
+    pub ticket_price: u64,
+    pub ticket_count: u16,
+    // Base pool of every round, unclaimed prizes roll over on top of it
+    pub prize_pool: u64,
+    pub ticket_prize: u64,
+    // Shorter than round_duration, so a round's claims have lapsed by the time its pool rolls over
+    pub claim_window: i64,
+    pub round_duration: i64,
+    // Round currently selling tickets
+    pub current_round: u64,
+}
+
+impl DCLotterySchedule {
+    // Opens round with the schedule terms, selling until round_duration from now
+    pub fn open_round(&self, round: u64, rollover: u64, now: i64) -> Result<DCLottery> {
+        let prize_pool = self
+            .prize_pool
+            .checked_add(rollover)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let mut lottery = DCLottery::new(
+            round,
+            self.ticket_price,
+            self.ticket_count,
+            prize_pool,
+            self.ticket_prize,
+            self.claim_window,
+        )?;
+        lottery.is_round = true;
+        lottery.closes_at = now
+            .checked_add(self.round_duration)
+            .ok_or(ErrorCode::MathOverflow)?;
+        lottery.rollover = rollover;
+        Ok(lottery)
+    }
+}
+
+// Keyed by the purchase computation, the ticket number is assigned once the
+// purchase succeeds so failed purchases never leave a gap
+#[account]
+#[derive(InitSpace)]
+pub struct DCLotteryTicket {
+    pub lottery: Pubkey,
+    // Default for bearer tickets, any wallet holding the proof's key can claim them
+    pub owner: Pubkey,
+    pub computation_offset: u64,
+    pub ticket_number: u16,
+    pub status: TicketStatus,
+    // computation_offset encrypted under the buyer's owner_x25519 (Enc<Shared, u64>), the
+    // claim circuits decrypt it with the key given in the TicketClaim and compare
+    pub owner_proof_nonce: u128,
+    pub owner_proof: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum TicketStatus {
+    // Purchase queued, waiting for the callback
+    Pending,
+    // Paid for, ticket_number is valid
+    Active,
+    // Prize claimed, either on its own or as part of the jackpot
+    Redeemed,
+}
+
+// Enc<Shared, { ticket_tag: u64, claimer: u128 }> under pubkey, the key the tickets'
+// owner proofs are encrypted with. claimer is claimer_tag of the claiming wallet, only the
+// holder of the key's secret can produce it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TicketClaim {
+    pub pubkey: [u8; 32],
+    pub nonce: u128,
+    pub ticket_tag: [u8; 32],
+    pub claimer: [u8; 32],
+}
+
+impl DCLotteryTicket {
+    pub fn is_bearer(&self) -> bool {
+        self.owner == Pubkey::default()
+    }
+
+    // Unredeemed ticket of this lottery held by owner, the key is checked in MPC
+    pub fn require_redeemable(&self, lottery: &Pubkey, owner: &Pubkey) -> Result<()> {
+        if self.lottery != *lottery || (!self.is_bearer() && self.owner != *owner) {
+            return Err(ErrorCode::LotteryTicketInvalid.into());
+        }
+        match self.status {
+            TicketStatus::Active => Ok(()),
+            TicketStatus::Redeemed => Err(ErrorCode::LotteryTicketRedeemed.into()),
+            TicketStatus::Pending => Err(ErrorCode::LotteryTicketInvalid.into()),
+        }
+    }
+
+    // Unredeemed ticket of any lottery held by owner, checked again in MPC
+    pub fn require_rekeyable(&self, owner: &Pubkey) -> Result<()> {
+        self.require_redeemable(&self.lottery, owner)
+    }
+}
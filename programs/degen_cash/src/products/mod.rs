@@ -1,2 +1,8 @@
 pub mod base;
 pub use base::*;
+
+pub mod escrow;
+pub use escrow::*;
+
+pub mod lotto;
+pub use lotto::*;
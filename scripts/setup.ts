@@ -66,6 +66,81 @@ function readKeypairFromFile(path: string): Keypair {
 	return Keypair.fromSecretKey(secretKey);
 }
 
+// Arcium nodes run in docker on localnet and fetch circuits from serve_circuits.sh
+const LOCALNET_CIRCUITS_URL = 'http://host.docker.internal:3131/';
+
+async function initCircuitConfig(
+	program: Program<DegenCash>,
+	owner: Keypair,
+	baseUrl: string
+): Promise<string> {
+	const [circuitConfigPDA] = PublicKey.findProgramAddressSync(
+		[Buffer.from('dc_circuit_config')],
+		program.programId
+	);
+
+	const existing = await program.provider.connection.getAccountInfo(circuitConfigPDA);
+	if (existing) {
+		console.log('  ✓ circuit config already exists');
+		return '';
+	}
+
+	const sig = await program.methods
+		.initCircuitConfig(baseUrl, '_testnet')
+		.accounts({
+			payer: owner.publicKey
+		})
+		.signers([owner])
+		.rpc({ commitment: 'confirmed' });
+
+	console.log(`  ✓ circuit config initialized: ${baseUrl}`);
+	return sig;
+}
+
+// Comp defs added after the core set share the same accounts, only the circuit differs
+async function initProductCompDefs(program: Program<DegenCash>, owner: Keypair): Promise<void> {
+	const baseSeedCompDefAcc = getArciumAccountBaseSeed('ComputationDefinitionAccount');
+	const compDefs = [
+		['close_user_dc_balance', () => program.methods.initCloseDcTokenAccountCompDef()],
+		['rotate_dc_key', () => program.methods.initRotateDcKeyCompDef()],
+		['confidential_transfer', () => program.methods.initConfidentialTransferCompDef()],
+		['sub_account_transfer', () => program.methods.initSubAccountTransferCompDef()],
+		['send_claimable_transfer', () => program.methods.initSendClaimableTransferCompDef()],
+		['claim_transfer', () => program.methods.initClaimTransferCompDef()],
+		['create_escrow', () => program.methods.initCreateEscrowCompDef()],
+		['release_escrow', () => program.methods.initReleaseEscrowCompDef()],
+		['claim_escrow', () => program.methods.initClaimEscrowCompDef()],
+		['expire_escrow', () => program.methods.initExpireEscrowCompDef()],
+		['buy_ticket', () => program.methods.initBuyTicketCompDef()],
+		['draw_lottery', () => program.methods.initDrawLotteryCompDef()],
+		['redeem_ticket', () => program.methods.initRedeemTicketCompDef()],
+		['rekey_ticket', () => program.methods.initRekeyTicketCompDef()],
+		['claim_jackpot', () => program.methods.initClaimJackpotCompDef()]
+	] as const;
+
+	for (const [circuit, method] of compDefs) {
+		const compDefPDA = PublicKey.findProgramAddressSync(
+			[baseSeedCompDefAcc, program.programId.toBuffer(), getCompDefAccOffset(circuit)],
+			getArciumProgAddress()
+		)[0];
+
+		if (await program.provider.connection.getAccountInfo(compDefPDA)) {
+			console.log(`  ✓ ${circuit} comp def already exists`);
+			continue;
+		}
+
+		await method()
+			.accounts({
+				compDefAccount: compDefPDA,
+				payer: owner.publicKey,
+				mxeAccount: getMXEAccAddress(program.programId)
+			})
+			.signers([owner])
+			.rpc({ commitment: 'confirmed' });
+		console.log(`  ✓ ${circuit} comp def initialized`);
+	}
+}
+
 async function initGlobalDCMintCompDef(
 	program: Program<DegenCash>,
 	owner: Keypair
@@ -372,12 +447,24 @@ async function main() {
 	console.log('  Step 2: Initializing Computation Definitions');
 	console.log(`${'─'.repeat(60)}\n`);
 
+	// Comp defs read their circuit source from the circuit config
+	const circuitsBaseUrl =
+		config.network === 'localhost'
+			? LOCALNET_CIRCUITS_URL
+			: envContent.match(/PUBLIC_CIRCUITS_BASE_URL=(.+)/)?.[1]?.trim();
+	if (!circuitsBaseUrl) {
+		console.error('❌ PUBLIC_CIRCUITS_BASE_URL must be set in app/.env for devnet');
+		process.exit(1);
+	}
+
 	try {
+		await initCircuitConfig(program, owner, circuitsBaseUrl);
 		await initGlobalDCMintCompDef(program, owner);
 		await initDepositCompDef(program, owner);
 		await initCreateDcTokenAccountCompDef(program, owner);
 		await initTransferCompDef(program, owner);
 		await initWithdrawCompDef(program, owner);
+		await initProductCompDefs(program, owner);
 		console.log('\n  ✓ All computation definitions initialized\n');
 	} catch (error) {
 		console.error('❌ Error initializing comp defs:', error);
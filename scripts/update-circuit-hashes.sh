@@ -0,0 +1,19 @@
+#!/bin/bash
+
+# Regenerate programs/degen_cash/circuits.sha256 from the artifacts in build/
+# Run this after `arcium build` whenever a circuit in encrypted-ixs changes
+
+set -e
+
+cd "$(dirname "$0")/.."
+
+if ! ls build/*.arcis > /dev/null 2>&1; then
+  echo "❌ Error: no circuit artifacts found in build/."
+  echo "   Run 'arcium build' first to generate them."
+  exit 1
+fi
+
+(cd build && sha256sum *.arcis) > programs/degen_cash/circuits.sha256
+
+echo "✓ Updated programs/degen_cash/circuits.sha256"
+cat programs/degen_cash/circuits.sha256
@@ -18,7 +18,7 @@ import {
   RescueCipher,
 } from "@arcium-hq/client";
 import { PublicKey, Keypair } from "@solana/web3.js";
-import { createMint, getOrCreateAssociatedTokenAccount, getAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { createMint, getOrCreateAssociatedTokenAccount, getAssociatedTokenAddressSync, getAccount, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as fs from "fs";
 import * as os from "os";
 import nacl from "tweetnacl";
@@ -101,6 +101,37 @@ describe("Degen Cash", () => {
     return account.amount.toString();
   }
 
+  // Accounts every init_*_comp_def instruction needs
+  function compDefAccounts(circuit: string) {
+    return {
+      compDefAccount: PublicKey.findProgramAddressSync(
+        [
+          getArciumAccountBaseSeed("ComputationDefinitionAccount"),
+          program.programId.toBuffer(),
+          getCompDefAccOffset(circuit),
+        ],
+        getArciumProgAddress()
+      )[0],
+      payer: owner.publicKey,
+      mxeAccount: getMXEAccAddress(program.programId),
+    };
+  }
+
+  // Arcium accounts every queue_* instruction needs
+  function queueAccounts(computationOffset: anchor.BN, circuit: string) {
+    return {
+      computationAccount: getComputationAccAddress(program.programId, computationOffset),
+      clusterAccount: arciumEnv.arciumClusterPubkey,
+      mxeAccount: getMXEAccAddress(program.programId),
+      mempoolAccount: getMempoolAccAddress(program.programId),
+      executingPool: getExecutingPoolAccAddress(program.programId),
+      compDefAccount: getCompDefAccAddress(
+        program.programId,
+        Buffer.from(getCompDefAccOffset(circuit)).readUInt32LE()
+      ),
+    };
+  }
+
   before(async () => {
     logBox("DEGEN CASH COMPREHENSIVE TEST SUITE");
     testLog = [];
@@ -120,16 +151,112 @@ describe("Degen Cash", () => {
     log(`✓ Deposit mint created: ${depositMint.toBase58()}`);
 
     logSection("Setup: Initializing Computation Definitions");
+    await program.methods
+      .initCircuitConfig("http://host.docker.internal:3131/", "_testnet")
+      .accounts({ payer: owner.publicKey })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ circuit config");
     await initGlobalDCMintCompDef(program, owner);
     log("✓ init_global_dc_mint comp def");
     await initDepositCompDef(program, owner);
     log("✓ deposit comp def");
     await initCreateDcTokenAccountCompDef(program, owner);
     log("✓ create_dc_token_account comp def");
+    await program.methods
+      .initCloseDcTokenAccountCompDef()
+      .accounts(compDefAccounts("close_user_dc_balance"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ close_dc_token_account comp def");
+    await program.methods
+      .initRotateDcKeyCompDef()
+      .accounts(compDefAccounts("rotate_dc_key"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ rotate_dc_key comp def");
+    await program.methods
+      .initSubAccountTransferCompDef()
+      .accounts(compDefAccounts("sub_account_transfer"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ sub_account_transfer comp def");
+    await program.methods
+      .initSendClaimableTransferCompDef()
+      .accounts(compDefAccounts("send_claimable_transfer"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ send_claimable_transfer comp def");
+    await program.methods
+      .initClaimTransferCompDef()
+      .accounts(compDefAccounts("claim_transfer"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ claim_transfer comp def");
     await initTransferCompDef(program, owner);
     log("✓ transfer comp def");
+    await program.methods
+      .initConfidentialTransferCompDef()
+      .accounts(compDefAccounts("confidential_transfer"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ confidential_transfer comp def");
     await initWithdrawCompDef(program, owner);
     log("✓ withdraw comp def");
+    await program.methods
+      .initCreateEscrowCompDef()
+      .accounts(compDefAccounts("create_escrow"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ create_escrow comp def");
+    await program.methods
+      .initReleaseEscrowCompDef()
+      .accounts(compDefAccounts("release_escrow"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ release_escrow comp def");
+    await program.methods
+      .initClaimEscrowCompDef()
+      .accounts(compDefAccounts("claim_escrow"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ claim_escrow comp def");
+    await program.methods
+      .initExpireEscrowCompDef()
+      .accounts(compDefAccounts("expire_escrow"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ expire_escrow comp def");
+    await program.methods
+      .initBuyTicketCompDef()
+      .accounts(compDefAccounts("buy_ticket"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ buy_ticket comp def");
+    await program.methods
+      .initDrawLotteryCompDef()
+      .accounts(compDefAccounts("draw_lottery"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ draw_lottery comp def");
+    await program.methods
+      .initRedeemTicketCompDef()
+      .accounts(compDefAccounts("redeem_ticket"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ redeem_ticket comp def");
+    await program.methods
+      .initRekeyTicketCompDef()
+      .accounts(compDefAccounts("rekey_ticket"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ rekey_ticket comp def");
+    await program.methods
+      .initClaimJackpotCompDef()
+      .accounts(compDefAccounts("claim_jackpot"))
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    log("✓ claim_jackpot comp def");
 
     logSection("Setup: Creating Users and Funding");
     const numUsers = 4;
@@ -225,15 +352,17 @@ describe("Degen Cash", () => {
         .queueCreateDcTokenAccount(
           createDcTokenComputationOffset,
           Array.from(user.x25519PublicKey),
-          new anchor.BN(deserializeLE(createDcTokenNonce).toString())
+          new anchor.BN(deserializeLE(createDcTokenNonce).toString()),
+          0
         )
-        .accounts({
+        .accountsPartial({
           computationAccount: getComputationAccAddress(
             program.programId,
             createDcTokenComputationOffset
           ),
           clusterAccount: arciumEnv.arciumClusterPubkey,
           payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
           mxeAccount: getMXEAccAddress(program.programId),
           mempoolAccount: getMempoolAccAddress(program.programId),
           executingPool: getExecutingPoolAccAddress(program.programId),
@@ -285,6 +414,7 @@ describe("Degen Cash", () => {
           ),
           clusterAccount: arciumEnv.arciumClusterPubkey,
           payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
           mxeAccount: getMXEAccAddress(program.programId),
           mempoolAccount: getMempoolAccAddress(program.programId),
           executingPool: getExecutingPoolAccAddress(program.programId),
@@ -340,6 +470,7 @@ describe("Degen Cash", () => {
         [Buffer.from("dc_global_mint")],
         program.programId
       );
+      const depositAta = getAssociatedTokenAddressSync(depositMint, dcGlobalMintPDA, true);
 
       await program.methods
         .queueTransfer(
@@ -365,7 +496,7 @@ describe("Degen Cash", () => {
           dcGlobalMintAccount: dcGlobalMintPDA,
           dcUserTokenAccount: sender.dcTokenAccount,
           receiverDcUserTokenAccount: receiver.dcTokenAccount,
-          depositMint: depositMint,
+          depositAta: depositAta,
         })
         .signers([sender.keypair])
         .rpc({ skipPreflight: false, commitment: "confirmed" });
@@ -382,9 +513,10 @@ describe("Degen Cash", () => {
       log(`  Sender:   ${Number(senderBefore) / 1_000_000} → ${Number(senderAfter) / 1_000_000}`);
       log(`  Receiver: ${Number(receiverBefore) / 1_000_000} → ${Number(receiverAfter) / 1_000_000}`);
       log(`  Variance: ${actualVariance}/255 (Cost: ${(varianceCost / 1_000_000).toFixed(2)} DC)`);
-      log(`  Status: ${event.statusCode === 0 ? '✓ Success' : '✗ Failed'}\n`);
+      log(`  Reserves: ${Number(event.globalReserves) / 1_000_000} USDC`);
+      log(`  Status: ${"success" in event.status ? '✓ Success' : '✗ Failed'}\n`);
 
-      expect(event.statusCode).to.equal(0);
+      expect(event.status).to.deep.equal({ success: {} });
     }
   });
 
@@ -455,144 +587,1725 @@ describe("Degen Cash", () => {
 
       log(`  DC:   ${Number(dcBefore) / 1_000_000} → ${Number(dcAfter) / 1_000_000} (Δ -${withdrawAmounts[i]})`);
       log(`  USDC: ${Number(splBefore) / 1_000_000} → ${Number(splAfter) / 1_000_000} (Δ +${withdrawAmounts[i]})`);
-      log(`  Status: ${event.statusCode === 0 ? '✓ Success' : '✗ Failed'}\n`);
+      log(`  Status: ${"success" in event.status ? '✓ Success' : '✗ Failed'}\n`);
 
-      expect(event.statusCode).to.equal(0);
+      expect(event.status).to.deep.equal({ success: {} });
       expect(Number(splAfter) - Number(splBefore)).to.equal(amount);
     }
   });
 
-  it("Should show final balances", async () => {
-    logBox("FINAL BALANCES");
+  it("Should reject a second computation while one is in flight", async () => {
+    logBox("TEST 4: COMPUTATION LOCK");
 
-    for (let i = 0; i < users.length; i++) {
-      const user = users[i];
-      const dcBalance = await getDecryptedBalance(user);
-      const splBalance = await getSPLBalance(user.ata);
+    const user = users[0];
+    const amount = 10 * 1_000_000;
 
-      log(`User ${i + 1}:`);
-      log(`  DC:   ${(Number(dcBalance) / 1_000_000).toFixed(2)} DC`);
-      log(`  USDC: ${(Number(splBalance) / 1_000_000).toFixed(2)} USDC`);
-      log(``);
-    }
-  });
+    const queueDeposit = (computationOffset: anchor.BN) =>
+      program.methods
+        .queueDeposit(computationOffset, new anchor.BN(amount))
+        .accountsPartial({
+          computationAccount: getComputationAccAddress(
+            program.programId,
+            computationOffset
+          ),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("deposit")).readUInt32LE()
+          ),
+          depositMint: depositMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
 
-  async function initTransferCompDef(
-    program: Program<DegenCash>,
-    owner: anchor.web3.Keypair
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
-    );
-    const offset = getCompDefAccOffset("transfer");
+    const dcBefore = await getDecryptedBalance(user);
+    const eventPromise = awaitEvent("depositEvent");
+    await queueDeposit(new anchor.BN(randomBytes(8), "hex"));
 
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
+    let rejected = false;
+    try {
+      await queueDeposit(new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      rejected = e.toString().includes("ComputationInFlight");
+    }
+    log(`  Second deposit rejected: ${rejected ? '✓' : '✗'}`);
 
-    const sig = await program.methods
-      .initTransferCompDef()
-      .accounts({
-        compDefAccount: compDefPDA,
-        payer: owner.publicKey,
-        mxeAccount: getMXEAccAddress(program.programId),
-      })
-      .signers([owner])
-      .rpc({ commitment: "confirmed" });
+    await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
 
-    return sig;
-  }
+    const dcAfter = await getDecryptedBalance(user);
+    log(`  DC: ${Number(dcBefore) / 1_000_000} → ${Number(dcAfter) / 1_000_000} (Δ +10)\n`);
 
-  async function initWithdrawCompDef(
-    program: Program<DegenCash>,
-    owner: anchor.web3.Keypair
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
-    );
-    const offset = getCompDefAccOffset("withdraw");
+    expect(rejected).to.equal(true);
+    expect(BigInt(dcAfter) - BigInt(dcBefore)).to.equal(BigInt(amount));
+  });
 
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
+  it("Should reject deposits and withdrawals with a foreign mint", async () => {
+    logBox("TEST 5: FOREIGN MINT");
 
-    const sig = await program.methods
-      .initWithdrawCompDef()
-      .accounts({
-        compDefAccount: compDefPDA,
-        payer: owner.publicKey,
-        mxeAccount: getMXEAccAddress(program.programId),
-      })
-      .signers([owner])
-      .rpc({ commitment: "confirmed" });
+    const user = users[0];
+    const amount = 10 * 1_000_000;
 
-    return sig;
-  }
+    const foreignMint = await createMint(
+      program.provider.connection,
+      owner,
+      owner.publicKey,
+      owner.publicKey,
+      6
+    );
+    const foreignUserAta = await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      owner,
+      foreignMint,
+      user.keypair.publicKey
+    );
+    await mintTo(
+      program.provider.connection,
+      owner,
+      foreignMint,
+      foreignUserAta.address,
+      owner,
+      amount
+    );
 
-  async function initGlobalDCMintCompDef(
-    program: Program<DegenCash>,
-    owner: anchor.web3.Keypair
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
+    const [dcGlobalMintPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_global_mint")],
+      program.programId
+    );
+    const foreignGlobalAta = await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      owner,
+      foreignMint,
+      dcGlobalMintPDA,
+      true
     );
-    const offset = getCompDefAccOffset("init_global_dc_mint");
 
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
+    logSection("Deposit with foreign mint");
+    const depositComputationOffset = new anchor.BN(randomBytes(8), "hex");
+    let depositRejected = false;
+    try {
+      await program.methods
+        .queueDeposit(depositComputationOffset, new anchor.BN(amount))
+        .accountsPartial({
+          computationAccount: getComputationAccAddress(
+            program.programId,
+            depositComputationOffset
+          ),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("deposit")).readUInt32LE()
+          ),
+          depositAta: foreignGlobalAta.address,
+          fromAta: foreignUserAta.address,
+          depositMint: foreignMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    } catch (e) {
+      depositRejected = e.toString().includes("InvalidDepositMint");
+    }
+    log(`  Deposit rejected: ${depositRejected ? '✓' : '✗'}`);
 
-    const sig = await program.methods
-      .initGlobalDcMintCompDef()
-      .accounts({
-        compDefAccount: compDefPDA,
-        payer: owner.publicKey,
-        mxeAccount: getMXEAccAddress(program.programId),
-      })
-      .signers([owner])
-      .rpc({ commitment: "confirmed" });
+    logSection("Withdraw with foreign mint");
+    const withdrawComputationOffset = new anchor.BN(randomBytes(8), "hex");
+    let withdrawRejected = false;
+    try {
+      await program.methods
+        .queueWithdraw(withdrawComputationOffset, new anchor.BN(amount))
+        .accountsPartial({
+          computationAccount: getComputationAccAddress(
+            program.programId,
+            withdrawComputationOffset
+          ),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          payer: user.keypair.publicKey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("withdraw")).readUInt32LE()
+          ),
+          dcGlobalMintAccount: dcGlobalMintPDA,
+          dcUserTokenAccount: user.dcTokenAccount,
+          withdrawAta: foreignGlobalAta.address,
+          toAta: foreignUserAta.address,
+          withdrawMint: foreignMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    } catch (e) {
+      withdrawRejected = e.toString().includes("InvalidDepositMint");
+    }
+    log(`  Withdraw rejected: ${withdrawRejected ? '✓' : '✗'}\n`);
 
-    return sig;
-  }
+    expect(depositRejected).to.equal(true);
+    expect(withdrawRejected).to.equal(true);
+    expect(await getSPLBalance(foreignUserAta.address)).to.equal(amount.toString());
+  });
 
-  async function initDepositCompDef(
-    program: Program<DegenCash>,
-    owner: anchor.web3.Keypair
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
+  it("Should let only the admin tune the DC config", async () => {
+    logBox("TEST 6: CONFIG");
+
+    const [dcConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_config")],
+      program.programId
     );
-    const offset = getCompDefAccOffset("deposit");
+    const configBefore = await program.account.dcConfig.fetch(dcConfigPDA, "confirmed");
+    log(`  Admin: ${configBefore.admin.toBase58()}`);
+    log(`  Withdraw fee: ${configBefore.withdrawFeeBps} bps`);
+    log(`  Transfer fee curve: ${configBefore.transferMaxFeeBps} → ${configBefore.transferMinFeeBps} bps`);
+    log(`  Max transfer: ${Number(configBefore.maxTransferAmount) / 1_000_000} DC`);
 
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
+    expect(configBefore.admin.toBase58()).to.equal(owner.publicKey.toBase58());
 
-    const sig = await program.methods
-      .initDepositCompDef()
-      .accounts({
-        compDefAccount: compDefPDA,
-        payer: owner.publicKey,
-        mxeAccount: getMXEAccAddress(program.programId),
-      })
+    let nonAdminRejected = false;
+    try {
+      await program.methods
+        .setWithdrawFee(0)
+        .accounts({ admin: users[0].keypair.publicKey })
+        .signers([users[0].keypair])
+        .rpc({ commitment: "confirmed" });
+    } catch (e) {
+      nonAdminRejected = true;
+    }
+    log(`  Non-admin update rejected: ${nonAdminRejected ? '✓' : '✗'}`);
+    expect(nonAdminRejected).to.equal(true);
+
+    await program.methods
+      .setWithdrawFee(100)
+      .accounts({ admin: owner.publicKey })
       .signers([owner])
       .rpc({ commitment: "confirmed" });
+    const configAfter = await program.account.dcConfig.fetch(dcConfigPDA, "confirmed");
+    log(`  Withdraw fee updated: ${configAfter.withdrawFeeBps} bps`);
+    expect(configAfter.withdrawFeeBps).to.equal(100);
 
-    return sig;
-  }
+    await program.methods
+      .setWithdrawFee(configBefore.withdrawFeeBps)
+      .accounts({ admin: owner.publicKey })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
 
-  async function initCreateDcTokenAccountCompDef(
-    program: Program<DegenCash>,
-    owner: anchor.web3.Keypair
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
-    );
-    const offset = getCompDefAccOffset("init_user_dc_balance");
+    let minDepositRejected = false;
+    try {
+      await program.methods
+        .setMinDepositAmount(configBefore.maxTransferAmount.addn(1))
+        .accounts({ admin: owner.publicKey })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+    } catch (e) {
+      minDepositRejected = e.toString().includes("InvalidConfig");
+    }
+    log(`  Min deposit above max transfer rejected: ${minDepositRejected ? '✓' : '✗'}\n`);
+    expect(minDepositRejected).to.equal(true);
+  });
+
+  it("Should block queueing while paused and resume after unpause", async () => {
+    logBox("TEST 7: PAUSE");
+
+    const user = users[0];
+    const amount = 10 * 1_000_000;
+
+    const queueDeposit = (computationOffset: anchor.BN) =>
+      program.methods
+        .queueDeposit(computationOffset, new anchor.BN(amount))
+        .accountsPartial({
+          computationAccount: getComputationAccAddress(
+            program.programId,
+            computationOffset
+          ),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("deposit")).readUInt32LE()
+          ),
+          depositMint: depositMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+
+    const pausedEvent = awaitEvent("pausedEvent");
+    await program.methods
+      .setPaused({ deposit: {} }, true)
+      .accounts({ pauseAuthority: owner.publicKey })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    await pausedEvent;
+    log("  ✓ Deposits paused");
+
+    let rejected = false;
+    try {
+      await queueDeposit(new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      rejected = e.toString().includes("OperationPaused");
+    }
+    log(`  Deposit while paused rejected: ${rejected ? '✓' : '✗'}`);
+    expect(rejected).to.equal(true);
+
+    const unpausedEvent = awaitEvent("unpausedEvent");
+    await program.methods
+      .setPaused({ deposit: {} }, false)
+      .accounts({ pauseAuthority: owner.publicKey })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+    await unpausedEvent;
+    log("  ✓ Deposits unpaused");
+
+    const eventPromise = awaitEvent("depositEvent");
+    await queueDeposit(new anchor.BN(randomBytes(8), "hex"));
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    log(`  Deposit after unpause: ${"success" in event.status ? '✓ Success' : '✗ Failed'}\n`);
+    expect(event.status).to.deep.equal({ success: {} });
+  });
+
+  it("Should record failed withdrawals in an operation receipt", async () => {
+    logBox("TEST 8: RECEIPTS");
+
+    const user = users[0];
+    const balance = await getDecryptedBalance(user);
+    // More than the user holds so the circuit fails
+    const amount = Number(balance) + 1_000 * 1_000_000;
+
+    const [dcGlobalMintPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_global_mint")],
+      program.programId
+    );
+    const withdrawAta = getAssociatedTokenAddressSync(depositMint, dcGlobalMintPDA, true);
+
+    const withdrawComputationOffset = new anchor.BN(randomBytes(8), "hex");
+    const [receiptPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dc_operation_receipt"),
+        user.keypair.publicKey.toBuffer(),
+        withdrawComputationOffset.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const eventPromise = awaitEvent("withdrawEvent");
+
+    await program.methods
+      .queueWithdraw(withdrawComputationOffset, new anchor.BN(amount))
+      .accountsPartial({
+        computationAccount: getComputationAccAddress(
+          program.programId,
+          withdrawComputationOffset
+        ),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        payer: user.keypair.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("withdraw")).readUInt32LE()
+        ),
+        dcGlobalMintAccount: dcGlobalMintPDA,
+        dcUserTokenAccount: user.dcTokenAccount,
+        withdrawAta: withdrawAta,
+        toAta: user.ata,
+        withdrawMint: depositMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        operationReceipt: receiptPDA,
+      })
+      .signers([user.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+
+    const pending = await program.account.dcOperationReceipt.fetch(receiptPDA);
+    log(`  Receipt queued: ${Object.keys(pending.status)[0]}`);
+
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const receipt = await program.account.dcOperationReceipt.fetch(receiptPDA);
+    log(`  Receipt finalized: ${Object.keys(receipt.status)[0]} (${Object.keys(receipt.circuitStatus)[0]})`);
+
+    expect(pending.status).to.deep.equal({ pending: {} });
+    expect(receipt.status).to.deep.equal({ failed: {} });
+    expect(receipt.circuitStatus).to.deep.equal({ insufficientFunds: {} });
+    expect(receipt.circuitStatus).to.deep.equal(event.status);
+    expect(receipt.computationOffset.toString()).to.equal(withdrawComputationOffset.toString());
+    expect(await getDecryptedBalance(user)).to.equal(balance);
+
+    logSection("Closing the receipt");
+    await program.methods
+      .closeOperationReceipt()
+      .accounts({
+        owner: user.keypair.publicKey,
+        operationReceipt: receiptPDA,
+      })
+      .signers([user.keypair])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await program.provider.connection.getAccountInfo(receiptPDA)).to.equal(null);
+    log("  ✓ Receipt closed\n");
+  });
+
+  it("Should lock DC in an escrow and release it once unlocked", async () => {
+    logBox("TEST 9: ESCROW");
+
+    const creator = users[1];
+    const beneficiary = users[2];
+    const amount = 10 * 1_000_000;
+    const now = Math.floor(Date.now() / 1000);
+
+    async function createEscrow(
+      escrowId: number,
+      unlockAt: number,
+      {
+        expiresAt = 0,
+        approvers = [] as PublicKey[],
+        threshold = 0,
+        hashLock = null as { pubkey: number[]; nonce: anchor.BN; ciphertext: number[] } | null,
+        heartbeatInterval = 0,
+      } = {}
+    ): Promise<PublicKey> {
+      const [escrowPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dc_escrow"),
+          creator.keypair.publicKey.toBuffer(),
+          new anchor.BN(escrowId).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const eventPromise = awaitEvent("escrowCreatedEvent");
+
+      await program.methods
+        .queueCreateEscrow(
+          computationOffset,
+          new anchor.BN(escrowId),
+          new anchor.BN(amount),
+          {
+            beneficiary: beneficiary.keypair.publicKey,
+            unlockAt: new anchor.BN(unlockAt),
+            expiresAt: new anchor.BN(expiresAt),
+            approvers,
+            threshold,
+            hashLock,
+            heartbeatInterval: new anchor.BN(heartbeatInterval),
+          }
+        )
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "create_escrow"),
+          payer: creator.keypair.publicKey,
+          dcUserTokenAccount: creator.dcTokenAccount,
+          escrowAccount: escrowPDA,
+        })
+        .signers([creator.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      expect(event.status).to.deep.equal({ success: {} });
+      return escrowPDA;
+    }
+
+    async function releaseEscrow(escrowPDA: PublicKey, payer: UserData) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      await program.methods
+        .queueReleaseEscrow(computationOffset)
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "release_escrow"),
+          payer: payer.keypair.publicKey,
+          escrowAccount: escrowPDA,
+          beneficiaryDcUserTokenAccount: beneficiary.dcTokenAccount,
+        })
+        .signers([payer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    }
+
+    logSection("Locked escrow");
+    const creatorBefore = await getDecryptedBalance(creator);
+    const lockedEscrow = await createEscrow(1, now + 3600);
+    const creatorAfter = await getDecryptedBalance(creator);
+    log(`  Creator: ${Number(creatorBefore) / 1_000_000} → ${Number(creatorAfter) / 1_000_000}`);
+    expect(Number(creatorBefore) - Number(creatorAfter)).to.equal(amount);
+
+    let releaseRejected = false;
+    try {
+      await releaseEscrow(lockedEscrow, users[3]);
+    } catch (e) {
+      releaseRejected = e.toString().includes("EscrowLocked");
+    }
+    log(`  Early release rejected: ${releaseRejected ? '✓' : '✗'}`);
+    expect(releaseRejected).to.equal(true);
+
+    logSection("Unlocked escrow");
+    const unlockedEscrow = await createEscrow(2, now - 60);
+    const beneficiaryBefore = await getDecryptedBalance(beneficiary);
+    const eventPromise = awaitEvent("escrowReleasedEvent");
+    // Anyone can release, the funds only go to the beneficiary
+    await releaseEscrow(unlockedEscrow, users[3]);
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const beneficiaryAfter = await getDecryptedBalance(beneficiary);
+
+    log(`  Beneficiary: ${Number(beneficiaryBefore) / 1_000_000} → ${Number(beneficiaryAfter) / 1_000_000}\n`);
+    expect(event.status).to.deep.equal({ success: {} });
+    expect(Number(beneficiaryAfter) - Number(beneficiaryBefore)).to.equal(amount);
+    expect(await program.provider.connection.getAccountInfo(unlockedEscrow)).to.equal(null);
+
+    logSection("2-of-3 escrow");
+    const approvers = [users[0], users[2], users[3]];
+    const multiSigEscrow = await createEscrow(3, now - 60, {
+      approvers: approvers.map((a) => a.keypair.publicKey),
+      threshold: 2,
+    });
+
+    async function approveEscrow(approver: UserData) {
+      await program.methods
+        .approveEscrow()
+        .accountsPartial({
+          approver: approver.keypair.publicKey,
+          escrowAccount: multiSigEscrow,
+        })
+        .signers([approver.keypair])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    await approveEscrow(approvers[0]);
+    let belowThresholdRejected = false;
+    try {
+      await releaseEscrow(multiSigEscrow, users[3]);
+    } catch (e) {
+      belowThresholdRejected = e.toString().includes("EscrowThresholdNotMet");
+    }
+    log(`  Release with 1/2 approvals rejected: ${belowThresholdRejected ? '✓' : '✗'}`);
+    expect(belowThresholdRejected).to.equal(true);
+
+    let duplicateRejected = false;
+    try {
+      await approveEscrow(approvers[0]);
+    } catch (e) {
+      duplicateRejected = e.toString().includes("EscrowAlreadyApproved");
+    }
+    log(`  Duplicate approval rejected: ${duplicateRejected ? '✓' : '✗'}`);
+    expect(duplicateRejected).to.equal(true);
+
+    await approveEscrow(approvers[2]);
+    const approved = await program.account.dcEscrow.fetch(multiSigEscrow, "confirmed");
+    expect(approved.approvals).to.equal(0b101);
+
+    const multiSigBefore = await getDecryptedBalance(beneficiary);
+    const multiSigEvent = awaitEvent("escrowReleasedEvent");
+    await releaseEscrow(multiSigEscrow, users[3]);
+    expect((await multiSigEvent).status).to.deep.equal({ success: {} });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const multiSigAfter = await getDecryptedBalance(beneficiary);
+    log(`  Beneficiary: ${Number(multiSigBefore) / 1_000_000} → ${Number(multiSigAfter) / 1_000_000}\n`);
+    expect(Number(multiSigAfter) - Number(multiSigBefore)).to.equal(amount);
+
+    logSection("Refunded escrow");
+    // Threshold never met and no expiry, only the creator can get it back
+    const unapprovedEscrow = await createEscrow(8, now - 60, {
+      approvers: [users[3].keypair.publicKey],
+      threshold: 1,
+    });
+    const refundBefore = await getDecryptedBalance(creator);
+    const refundEvent = awaitEvent("escrowReleasedEvent");
+    const refundOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .queueRefundEscrow(refundOffset)
+      .accountsPartial({
+        ...queueAccounts(refundOffset, "release_escrow"),
+        payer: creator.keypair.publicKey,
+        escrowAccount: unapprovedEscrow,
+        dcUserTokenAccount: creator.dcTokenAccount,
+      })
+      .signers([creator.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    const refunded = await refundEvent;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const refundAfter = await getDecryptedBalance(creator);
+    log(`  Creator: ${Number(refundBefore) / 1_000_000} → ${Number(refundAfter) / 1_000_000}\n`);
+    expect(refunded.status).to.deep.equal({ success: {} });
+    expect(refunded.beneficiary.toBase58()).to.equal(creator.keypair.publicKey.toBase58());
+    expect(Number(refundAfter) - Number(refundBefore)).to.equal(amount);
+    expect(await program.provider.connection.getAccountInfo(unapprovedEscrow)).to.equal(null);
+
+    logSection("Hash-locked escrow");
+    // Encrypts a u128 secret under the user's DC account key, the shape the program stores
+    function encryptSecret(user: UserData, secret: bigint) {
+      const nonce = randomBytes(16);
+      const cipher = new RescueCipher(x25519.getSharedSecret(user.x25519PrivateKey, mxePublicKey));
+      return {
+        ciphertext: cipher.encrypt([secret], nonce)[0],
+        nonce: new anchor.BN(deserializeLE(nonce).toString()),
+      };
+    }
+
+    const secret = deserializeLE(randomBytes(16));
+    const committed = encryptSecret(creator, secret);
+    const hashLockedEscrow = await createEscrow(5, now - 60, {
+      hashLock: {
+        pubkey: Array.from(creator.x25519PublicKey),
+        nonce: committed.nonce,
+        ciphertext: committed.ciphertext,
+      },
+    });
+
+    const claimer = users[3];
+    async function claimEscrow(claimSecret: bigint) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      // Secret and signing wallet under one nonce, see claimerTag
+      const nonce = randomBytes(16);
+      const cipher = new RescueCipher(x25519.getSharedSecret(claimer.x25519PrivateKey, mxePublicKey));
+      const [secretCiphertext, claimerCiphertext] = cipher.encrypt(
+        [claimSecret, claimerTag(claimer.keypair.publicKey)],
+        nonce
+      );
+      const eventPromise = awaitEvent("escrowClaimedEvent");
+      await program.methods
+        .queueClaimEscrow(computationOffset, {
+          nonce: new anchor.BN(deserializeLE(nonce).toString()),
+          secret: secretCiphertext,
+          claimer: claimerCiphertext,
+        })
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "claim_escrow"),
+          payer: claimer.keypair.publicKey,
+          escrowAccount: hashLockedEscrow,
+          claimerDcUserTokenAccount: claimer.dcTokenAccount,
+        })
+        .signers([claimer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      return event;
+    }
+
+    let hashLockedReleaseRejected = false;
+    try {
+      await releaseEscrow(hashLockedEscrow, users[3]);
+    } catch (e) {
+      hashLockedReleaseRejected = e.toString().includes("EscrowHashLocked");
+    }
+    log(`  Release of hash-locked escrow rejected: ${hashLockedReleaseRejected ? '✓' : '✗'}`);
+    expect(hashLockedReleaseRejected).to.equal(true);
+
+    const wrongClaim = await claimEscrow(secret + BigInt(1));
+    log(`  Wrong secret: ${Object.keys(wrongClaim.status)[0]}`);
+    expect(wrongClaim.status).to.deep.equal({ secretMismatch: {} });
+    expect(await program.provider.connection.getAccountInfo(hashLockedEscrow)).to.not.equal(null);
+
+    const claimerBefore = await getDecryptedBalance(claimer);
+    const claim = await claimEscrow(secret);
+    const claimerAfter = await getDecryptedBalance(claimer);
+    log(`  Claimer: ${Number(claimerBefore) / 1_000_000} → ${Number(claimerAfter) / 1_000_000}\n`);
+    expect(claim.status).to.deep.equal({ success: {} });
+    expect(Number(claimerAfter) - Number(claimerBefore)).to.equal(amount);
+    expect(await program.provider.connection.getAccountInfo(hashLockedEscrow)).to.equal(null);
+
+    logSection("Dead man's switch");
+    async function heartbeat(escrowPDA: PublicKey) {
+      await program.methods
+        .heartbeat()
+        .accountsPartial({
+          creator: creator.keypair.publicKey,
+          escrowAccount: escrowPDA,
+        })
+        .signers([creator.keypair])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const aliveSwitch = await createEscrow(6, now + 60, { heartbeatInterval: 3600 });
+    await heartbeat(aliveSwitch);
+    const aliveAccount = await program.account.dcEscrow.fetch(aliveSwitch, "confirmed");
+    log(`  Heartbeat pushed deadline to ${aliveAccount.unlockAt.toString()}`);
+    expect(aliveAccount.unlockAt.toNumber()).to.be.greaterThan(now + 3000);
+
+    let aliveReleaseRejected = false;
+    try {
+      await releaseEscrow(aliveSwitch, users[3]);
+    } catch (e) {
+      aliveReleaseRejected = e.toString().includes("EscrowLocked");
+    }
+    log(`  Release before deadline rejected: ${aliveReleaseRejected ? '✓' : '✗'}`);
+    expect(aliveReleaseRejected).to.equal(true);
+
+    const firedSwitch = await createEscrow(7, now - 60, { heartbeatInterval: 3600 });
+    let lateHeartbeatRejected = false;
+    try {
+      await heartbeat(firedSwitch);
+    } catch (e) {
+      lateHeartbeatRejected = e.toString().includes("EscrowDeadlinePassed");
+    }
+    log(`  Heartbeat after deadline rejected: ${lateHeartbeatRejected ? '✓' : '✗'}`);
+    expect(lateHeartbeatRejected).to.equal(true);
+
+    const switchBefore = await getDecryptedBalance(beneficiary);
+    const switchEvent = awaitEvent("escrowReleasedEvent");
+    await releaseEscrow(firedSwitch, users[3]);
+    expect((await switchEvent).status).to.deep.equal({ success: {} });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const switchAfter = await getDecryptedBalance(beneficiary);
+    log(`  Beneficiary: ${Number(switchBefore) / 1_000_000} → ${Number(switchAfter) / 1_000_000}\n`);
+    expect(Number(switchAfter) - Number(switchBefore)).to.equal(amount);
+
+    logSection("Expired escrow");
+    const expiredEscrow = await createEscrow(4, now - 120, { expiresAt: now - 60 });
+
+    let expiredReleaseRejected = false;
+    try {
+      await releaseEscrow(expiredEscrow, users[3]);
+    } catch (e) {
+      expiredReleaseRejected = e.toString().includes("EscrowExpired");
+    }
+    log(`  Release after expiry rejected: ${expiredReleaseRejected ? '✓' : '✗'}`);
+    expect(expiredReleaseRejected).to.equal(true);
+
+    const [dcGlobalMintPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_global_mint")],
+      program.programId
+    );
+    const expiredBefore = await getDecryptedBalance(beneficiary);
+    const expireOffset = new anchor.BN(randomBytes(8), "hex");
+    const expiredEvent = awaitEvent("escrowExpiredEvent");
+    // Anyone can burn an expired escrow
+    await program.methods
+      .queueExpireEscrow(expireOffset)
+      .accountsPartial({
+        ...queueAccounts(expireOffset, "expire_escrow"),
+        payer: users[3].keypair.publicKey,
+        escrowAccount: expiredEscrow,
+        dcGlobalMintAccount: dcGlobalMintPDA,
+      })
+      .signers([users[3].keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    expect((await expiredEvent).status).to.deep.equal({ success: {} });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    log(`  Escrow burned, beneficiary unchanged\n`);
+    expect(await program.provider.connection.getAccountInfo(expiredEscrow)).to.equal(null);
+    expect(await getDecryptedBalance(beneficiary)).to.equal(expiredBefore);
+  });
+
+  const ticketPrice = 10 * 1_000_000;
+  const ticketPrize = 20 * 1_000_000;
+  const prizePool = 100 * 1_000_000;
+  const claimWindow = 24 * 60 * 60;
+
+  function dcGlobalMintAddress(): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_global_mint")],
+      program.programId
+    )[0];
+  }
+
+  function lotteryAddress(lotteryId: anchor.BN): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_lottery"), lotteryId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  }
+
+  async function createLottery(lotteryId: anchor.BN, admin: Keypair) {
+    await program.methods
+      .createLottery(
+        lotteryId,
+        new anchor.BN(ticketPrice),
+        5,
+        new anchor.BN(prizePool),
+        new anchor.BN(ticketPrize),
+        new anchor.BN(claimWindow)
+      )
+      .accounts({ admin: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function buyTicket(
+    lotteryPDA: PublicKey,
+    buyer: UserData,
+    computationOffset: anchor.BN,
+    bearer = false
+  ): Promise<PublicKey> {
+    const [ticketPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dc_lottery_ticket"),
+        lotteryPDA.toBuffer(),
+        computationOffset.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    await program.methods
+      .queueBuyTicket(computationOffset, bearer)
+      .accountsPartial({
+        ...queueAccounts(computationOffset, "buy_ticket"),
+        payer: buyer.keypair.publicKey,
+        dcGlobalMintAccount: dcGlobalMintAddress(),
+        dcUserTokenAccount: buyer.dcTokenAccount,
+        lotteryAccount: lotteryPDA,
+        ticketAccount: ticketPDA,
+      })
+      .signers([buyer.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    return ticketPDA;
+  }
+
+  // TicketClaim for the ticket tagged ticketTag, made with the holder's key out to signer
+  function ticketClaim(holder: UserData, signer: PublicKey, ticketTag: anchor.BN) {
+    const nonce = randomBytes(16);
+    const cipher = new RescueCipher(x25519.getSharedSecret(holder.x25519PrivateKey, mxePublicKey));
+    const [ticketTagCiphertext, claimerCiphertext] = cipher.encrypt(
+      [BigInt(ticketTag.toString()), claimerTag(signer)],
+      nonce
+    );
+    return {
+      pubkey: Array.from(holder.x25519PublicKey),
+      nonce: new anchor.BN(deserializeLE(nonce).toString()),
+      ticketTag: ticketTagCiphertext,
+      claimer: claimerCiphertext,
+    };
+  }
+
+  async function drawLottery(lotteryPDA: PublicKey) {
+    const drawOffset = new anchor.BN(randomBytes(8), "hex");
+    const drawEvent = awaitEvent("lotteryDrawnEvent");
+    await program.methods
+      .queueDrawLottery(drawOffset)
+      .accountsPartial({
+        ...queueAccounts(drawOffset, "draw_lottery"),
+        payer: owner.publicKey,
+        admin: owner.publicKey,
+        lotteryAccount: lotteryPDA,
+      })
+      .signers([owner])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    const drawn = await drawEvent;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    return drawn;
+  }
+
+  // Winning tickets of the first lottery, redeemed in TEST 11
+  const lotteryTickets: PublicKey[] = [];
+
+  it("Should sell lottery tickets and draw the winners", async () => {
+    logBox("TEST 10: LOTTERY");
+
+    const lotteryPDA = lotteryAddress(new anchor.BN(1));
+
+    logSection("Create lottery");
+    await createLottery(new anchor.BN(1), owner);
+
+    let nonAdminRejected = false;
+    try {
+      await createLottery(new anchor.BN(2), users[0].keypair);
+    } catch (e) {
+      nonAdminRejected = true;
+    }
+    log(`  Non-admin create rejected: ${nonAdminRejected ? '✓' : '✗'}`);
+    expect(nonAdminRejected).to.equal(true);
+
+    logSection("Buy tickets");
+    const buyers = [users[0], users[1], users[2], users[3], users[0]];
+    for (const [i, buyer] of buyers.entries()) {
+      const before = await getDecryptedBalance(buyer);
+      const eventPromise = awaitEvent("ticketPurchasedEvent");
+      const ticketPDA = await buyTicket(lotteryPDA, buyer, new anchor.BN(randomBytes(8), "hex"));
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const after = await getDecryptedBalance(buyer);
+      const ticket = await program.account.dcLotteryTicket.fetch(ticketPDA, "confirmed");
+      lotteryTickets.push(ticketPDA);
+
+      log(`  Ticket #${ticket.ticketNumber}: ${Number(before) / 1_000_000} → ${Number(after) / 1_000_000} DC`);
+      expect(event.status).to.deep.equal({ success: {} });
+      expect(Number(before) - Number(after)).to.equal(ticketPrice);
+      expect(ticket.ticketNumber).to.equal(i);
+      expect(ticket.owner.toBase58()).to.equal(buyer.keypair.publicKey.toBase58());
+      expect(ticket.status).to.deep.equal({ active: {} });
+    }
+
+    const lottery = await program.account.dcLottery.fetch(lotteryPDA, "confirmed");
+    expect(lottery.ticketsSold).to.equal(buyers.length);
+
+    let soldOutRejected = false;
+    try {
+      await buyTicket(lotteryPDA, users[2], new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      soldOutRejected = e.toString().includes("LotterySoldOut");
+    }
+    log(`  Purchase after sell out rejected: ${soldOutRejected ? '✓' : '✗'}`);
+    expect(soldOutRejected).to.equal(true);
+
+    logSection("Draw");
+    const drawn = await drawLottery(lotteryPDA);
+
+    const drawnLottery = await program.account.dcLottery.fetch(lotteryPDA, "confirmed");
+    log(`  Winning tickets: ${drawnLottery.winningTickets.join(", ")}\n`);
+    expect(drawn.status).to.deep.equal({ success: {} });
+    expect(drawnLottery.status).to.deep.equal({ drawn: {} });
+    expect(drawnLottery.drawnAt.toNumber()).to.be.greaterThan(0);
+    // Every ticket sold wins when exactly 5 were sold
+    expect([...drawnLottery.winningTickets].sort()).to.deep.equal([0, 1, 2, 3, 4]);
+  });
+
+  it("Should redeem winning tickets once", async () => {
+    logBox("TEST 11: TICKET REDEMPTION");
+
+    const lotteryPDA = lotteryAddress(new anchor.BN(1));
+
+    async function redeemTicket(claimer: UserData, ticketPDA: PublicKey, computationOffset: anchor.BN) {
+      const { computationOffset: ticketTag } = await program.account.dcLotteryTicket.fetch(ticketPDA, "confirmed");
+      await program.methods
+        .queueRedeemTicket(computationOffset, ticketClaim(claimer, claimer.keypair.publicKey, ticketTag))
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "redeem_ticket"),
+          payer: claimer.keypair.publicKey,
+          dcGlobalMintAccount: dcGlobalMintAddress(),
+          dcUserTokenAccount: claimer.dcTokenAccount,
+          lotteryAccount: lotteryPDA,
+          ticketAccount: ticketPDA,
+        })
+        .signers([claimer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    }
+
+    logSection("Redeem ticket #1");
+    const claimer = users[1];
+    const before = await getDecryptedBalance(claimer);
+    const eventPromise = awaitEvent("ticketRedeemedEvent");
+    await redeemTicket(claimer, lotteryTickets[1], new anchor.BN(randomBytes(8), "hex"));
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const after = await getDecryptedBalance(claimer);
+    const ticket = await program.account.dcLotteryTicket.fetch(lotteryTickets[1], "confirmed");
+
+    log(`  DC: ${Number(before) / 1_000_000} → ${Number(after) / 1_000_000} (Δ +${ticketPrize / 1_000_000})`);
+    expect(event.status).to.deep.equal({ success: {} });
+    expect(Number(after) - Number(before)).to.equal(ticketPrize);
+    expect(ticket.status).to.deep.equal({ redeemed: {} });
+
+    let doubleClaimRejected = false;
+    try {
+      await redeemTicket(claimer, lotteryTickets[1], new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      doubleClaimRejected = e.toString().includes("LotteryTicketRedeemed");
+    }
+    log(`  Second redemption rejected: ${doubleClaimRejected ? '✓' : '✗'}`);
+    expect(doubleClaimRejected).to.equal(true);
+
+    let foreignTicketRejected = false;
+    try {
+      await redeemTicket(users[0], lotteryTickets[2], new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      foreignTicketRejected = e.toString().includes("LotteryTicketInvalid");
+    }
+    log(`  Redeeming someone else's ticket rejected: ${foreignTicketRejected ? '✓' : '✗'}\n`);
+    expect(foreignTicketRejected).to.equal(true);
+
+    const lottery = await program.account.dcLottery.fetch(lotteryPDA, "confirmed");
+    expect(lottery.prizesClaimed.toNumber()).to.equal(ticketPrize);
+  });
+
+  it("Should pay the jackpot to the holder of every bearer winning ticket", async () => {
+    logBox("TEST 12: BEARER JACKPOT");
+
+    const lotteryPDA = lotteryAddress(new anchor.BN(2));
+    const winner = users[2];
+
+    logSection("Buy every ticket as a bearer ticket");
+    await createLottery(new anchor.BN(2), owner);
+    const tickets: PublicKey[] = [];
+    for (let i = 0; i < 5; i++) {
+      const eventPromise = awaitEvent("ticketPurchasedEvent");
+      tickets.push(await buyTicket(lotteryPDA, winner, new anchor.BN(randomBytes(8), "hex"), true));
+      expect((await eventPromise).status).to.deep.equal({ success: {} });
+    }
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    // No owner recorded, whoever holds the winner's x25519 key can claim
+    for (const ticketPDA of tickets) {
+      const ticket = await program.account.dcLotteryTicket.fetch(ticketPDA, "confirmed");
+      expect(ticket.owner.toBase58()).to.equal(PublicKey.default.toBase58());
+    }
+    log(`  5 bearer tickets bought, no owner recorded ✓`);
+    expect((await drawLottery(lotteryPDA)).status).to.deep.equal({ success: {} });
+
+    // Tickets are numbered in purchase order, pass them in draw order
+    const drawnLottery = await program.account.dcLottery.fetch(lotteryPDA, "confirmed");
+    const [ticketAccount0, ticketAccount1, ticketAccount2, ticketAccount3, ticketAccount4] =
+      drawnLottery.winningTickets.map((n: number) => tickets[n]);
+    // The claim names the first winning ticket
+    const { computationOffset: jackpotTag } = await program.account.dcLotteryTicket.fetch(ticketAccount0, "confirmed");
+
+    async function claimJackpot(
+      claimer: UserData,
+      computationOffset: anchor.BN,
+      claim = ticketClaim(claimer, claimer.keypair.publicKey, jackpotTag)
+    ) {
+      await program.methods
+        .queueClaimJackpot(computationOffset, claim)
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "claim_jackpot"),
+          payer: claimer.keypair.publicKey,
+          dcGlobalMintAccount: dcGlobalMintAddress(),
+          dcUserTokenAccount: claimer.dcTokenAccount,
+          lotteryAccount: lotteryPDA,
+          ticketAccount0,
+          ticketAccount1,
+          ticketAccount2,
+          ticketAccount3,
+          ticketAccount4,
+        })
+        .signers([claimer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    }
+
+    logSection("Claim someone else's bearer tickets");
+    const thiefEvent = awaitEvent("jackpotClaimedEvent");
+    await claimJackpot(users[1], new anchor.BN(randomBytes(8), "hex"));
+    const stolen = await thiefEvent;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    log(`  Claim under the thief's key rejected: ${"notOwner" in stolen.status ? '✓' : '✗'}`);
+    expect(stolen.status).to.deep.equal({ notOwner: {} });
+
+    // Winner's claim copied from a pending transaction, made out to the winner's wallet
+    const replayEvent = awaitEvent("jackpotClaimedEvent");
+    await claimJackpot(
+      users[1],
+      new anchor.BN(randomBytes(8), "hex"),
+      ticketClaim(winner, winner.keypair.publicKey, jackpotTag)
+    );
+    const replayed = await replayEvent;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    log(`  Replayed claim rejected: ${"notOwner" in replayed.status ? '✓' : '✗'}`);
+    expect(replayed.status).to.deep.equal({ notOwner: {} });
+
+    logSection("Claim jackpot");
+    const before = await getDecryptedBalance(winner);
+    const eventPromise = awaitEvent("jackpotClaimedEvent");
+    await claimJackpot(winner, new anchor.BN(randomBytes(8), "hex"));
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const after = await getDecryptedBalance(winner);
+
+    log(`  DC: ${Number(before) / 1_000_000} → ${Number(after) / 1_000_000} (Δ +${prizePool / 1_000_000})`);
+    expect(event.status).to.deep.equal({ success: {} });
+    expect(Number(after) - Number(before)).to.equal(prizePool);
+    for (const ticketPDA of tickets) {
+      const ticket = await program.account.dcLotteryTicket.fetch(ticketPDA, "confirmed");
+      expect(ticket.status).to.deep.equal({ redeemed: {} });
+    }
+
+    let doubleClaimRejected = false;
+    try {
+      await claimJackpot(winner, new anchor.BN(randomBytes(8), "hex"));
+    } catch (e) {
+      doubleClaimRejected = e.toString().includes("LotteryTicketRedeemed");
+    }
+    log(`  Second jackpot claim rejected: ${doubleClaimRejected ? '✓' : '✗'}\n`);
+    expect(doubleClaimRejected).to.equal(true);
+  });
+
+  it("Should roll daily lottery rounds over with the crank", async () => {
+    logBox("TEST 13: LOTTERY ROUNDS");
+
+    const roundDuration = 10;
+    const roundClaimWindow = 5;
+    const [schedulePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_lottery_schedule")],
+      program.programId
+    );
+    const roundAddress = (round: number) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("dc_lottery_round"), new anchor.BN(round).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    logSection("Create schedule");
+    await program.methods
+      .createLotterySchedule(
+        new anchor.BN(ticketPrice),
+        5,
+        new anchor.BN(prizePool),
+        new anchor.BN(ticketPrize),
+        new anchor.BN(roundClaimWindow),
+        new anchor.BN(roundDuration)
+      )
+      .accounts({ admin: owner.publicKey })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    const round0 = await program.account.dcLottery.fetch(roundAddress(0), "confirmed");
+    log(`  Round 0 closes at ${round0.closesAt.toNumber()}`);
+    expect(round0.isRound).to.equal(true);
+    expect(round0.prizePool.toNumber()).to.equal(prizePool);
+
+    // Any wallet can crank, no admin signature needed
+    const cranker = users[3];
+    async function advanceRound(round: number) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      await program.methods
+        .queueAdvanceLotteryRound(computationOffset)
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "draw_lottery"),
+          payer: cranker.keypair.publicKey,
+          lotteryScheduleAccount: schedulePDA,
+          roundAccount: roundAddress(round),
+          previousRoundAccount: round > 0 ? roundAddress(round - 1) : null,
+          nextRoundAccount: roundAddress(round + 1),
+        })
+        .signers([cranker.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+    }
+
+    let earlyCrankRejected = false;
+    try {
+      await advanceRound(0);
+    } catch (e) {
+      earlyCrankRejected = e.toString().includes("LotteryRoundNotOver");
+    }
+    log(`  Crank before the round is over rejected: ${earlyCrankRejected ? '✓' : '✗'}`);
+    expect(earlyCrankRejected).to.equal(true);
+
+    logSection("Close rounds without ticket sales");
+    for (const round of [0, 1]) {
+      await new Promise((resolve) => setTimeout(resolve, (roundDuration + 2) * 1000));
+      await advanceRound(round);
+
+      const closed = await program.account.dcLottery.fetch(roundAddress(round), "confirmed");
+      const opened = await program.account.dcLottery.fetch(roundAddress(round + 1), "confirmed");
+      log(`  Round ${round} closed, round ${round + 1} pool: ${opened.prizePool.toNumber() / 1_000_000} DC`);
+      expect(closed.status).to.deep.equal({ closed: {} });
+      expect(opened.status).to.deep.equal({ open: {} });
+    }
+
+    // Round 0 never drew, so its whole pool rolled into round 2
+    const round2 = await program.account.dcLottery.fetch(roundAddress(2), "confirmed");
+    const schedule = await program.account.dcLotterySchedule.fetch(schedulePDA, "confirmed");
+    log(`  Round 2 rollover: ${round2.rollover.toNumber() / 1_000_000} DC\n`);
+    expect(round2.rollover.toNumber()).to.equal(prizePool);
+    expect(round2.prizePool.toNumber()).to.equal(2 * prizePool);
+    expect(schedule.currentRound.toNumber()).to.equal(2);
+  });
+
+  it("Should only close DC token accounts with a zero balance", async () => {
+    logBox("TEST 14: CLOSE ACCOUNT");
+
+    async function closeAccount(user: { keypair: Keypair; dcTokenAccount: PublicKey }) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const eventPromise = awaitEvent("dcTokenAccountClosedEvent");
+      await program.methods
+        .queueCloseDcTokenAccount(computationOffset)
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "close_user_dc_balance"),
+          payer: user.keypair.publicKey,
+          dcUserTokenAccount: user.dcTokenAccount,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      return event;
+    }
+
+    logSection("Close account with a balance");
+    const funded = users[3];
+    const kept = await closeAccount(funded);
+    log(`  Close refused: ${!kept.closed ? '✓' : '✗'}`);
+    expect(kept.closed).to.equal(false);
+    expect(await program.provider.connection.getAccountInfo(funded.dcTokenAccount)).to.not.equal(null);
+
+    logSection("Close empty account");
+    const keypair = anchor.web3.Keypair.generate();
+    const airdropSig = await program.provider.connection.requestAirdrop(
+      keypair.publicKey,
+      1_000_000_000
+    );
+    await program.provider.connection.confirmTransaction(airdropSig);
+    const x25519PrivateKey = nacl.sign.detached(Buffer.from("dgn.cash"), keypair.secretKey).slice(0, 32);
+    const [dcTokenAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_user_token_account"), keypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const createOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .queueCreateDcTokenAccount(
+        createOffset,
+        Array.from(x25519.getPublicKey(x25519PrivateKey)),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString()),
+        0
+      )
+      .accountsPartial({
+        ...queueAccounts(createOffset, "init_user_dc_balance"),
+        payer: keypair.publicKey,
+        dcUserTokenAccount: dcTokenAccount,
+      })
+      .signers([keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const lamportsBefore = await program.provider.connection.getBalance(keypair.publicKey);
+    const closed = await closeAccount({ keypair, dcTokenAccount });
+    const lamportsAfter = await program.provider.connection.getBalance(keypair.publicKey);
+    log(`  Closed, rent returned: ${(lamportsAfter - lamportsBefore) / 1_000_000_000} SOL\n`);
+    expect(closed.closed).to.equal(true);
+    expect(await program.provider.connection.getAccountInfo(dcTokenAccount)).to.equal(null);
+  });
+
+  it("Should rotate a DC token account's encryption key", async () => {
+    logBox("TEST 15: ROTATE KEY");
+
+    const user = users[3];
+    // Bearer ticket bought under the key that is about to be rotated away
+    const lotteryPDA = lotteryAddress(new anchor.BN(3));
+    await createLottery(new anchor.BN(3), owner);
+    const ticketOffset = new anchor.BN(randomBytes(8), "hex");
+    const ticketEvent = awaitEvent("ticketPurchasedEvent");
+    const ticketPDA = await buyTicket(lotteryPDA, user, ticketOffset, true);
+    expect((await ticketEvent).status).to.deep.equal({ success: {} });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const balanceBefore = await getDecryptedBalance(user);
+    const newX25519PrivateKey = x25519.utils.randomPrivateKey();
+    const newX25519PublicKey = x25519.getPublicKey(newX25519PrivateKey);
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const eventPromise = awaitEvent("dcKeyRotatedEvent");
+    await program.methods
+      .queueRotateDcKey(
+        computationOffset,
+        Array.from(newX25519PublicKey),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString())
+      )
+      .accountsPartial({
+        ...queueAccounts(computationOffset, "rotate_dc_key"),
+        payer: user.keypair.publicKey,
+        dcUserTokenAccount: user.dcTokenAccount,
+      })
+      .signers([user.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    const event = await eventPromise;
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    expect(Buffer.from(event.ownerX25519)).to.deep.equal(Buffer.from(newX25519PublicKey));
+
+    const dcAccount = await program.account.dcUserTokenAccount.fetch(user.dcTokenAccount, "confirmed");
+    expect(Buffer.from(dcAccount.ownerX25519)).to.deep.equal(Buffer.from(newX25519PublicKey));
+
+    // Keys the ticket is still held under
+    const oldHolder = { ...user };
+    user.x25519PrivateKey = newX25519PrivateKey;
+    user.x25519PublicKey = newX25519PublicKey;
+    const balanceAfter = await getDecryptedBalance(user);
+    log(`  Balance under new key: ${balanceAfter} (was ${balanceBefore}) ${balanceAfter === balanceBefore ? '✓' : '✗'}`);
+    expect(balanceAfter).to.equal(balanceBefore);
+
+    async function rekeyTicket(signer: UserData, claim: ReturnType<typeof ticketClaim>) {
+      const rekeyOffset = new anchor.BN(randomBytes(8), "hex");
+      const rekeyEvent = awaitEvent("ticketRekeyedEvent");
+      await program.methods
+        .queueRekeyTicket(rekeyOffset, claim, new anchor.BN(deserializeLE(randomBytes(16)).toString()))
+        .accountsPartial({
+          ...queueAccounts(rekeyOffset, "rekey_ticket"),
+          payer: signer.keypair.publicKey,
+          dcUserTokenAccount: signer.dcTokenAccount,
+          ticketAccount: ticketPDA,
+        })
+        .signers([signer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const rekeyed = await rekeyEvent;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      return rekeyed;
+    }
+
+    logSection("Rekey bearer ticket");
+    // Owner's claim copied from a pending transaction, made out to the owner's wallet
+    const replayed = await rekeyTicket(users[1], ticketClaim(oldHolder, user.keypair.publicKey, ticketOffset));
+    log(`  Replayed rekey rejected: ${"notOwner" in replayed.status ? '✓' : '✗'}`);
+    expect(replayed.status).to.deep.equal({ notOwner: {} });
+
+    const rekeyed = await rekeyTicket(user, ticketClaim(oldHolder, user.keypair.publicKey, ticketOffset));
+    expect(rekeyed.status).to.deep.equal({ success: {} });
+
+    // The proof now decrypts back to the ticket tag under the new key
+    const ticket = await program.account.dcLotteryTicket.fetch(ticketPDA, "confirmed");
+    const cipher = new RescueCipher(x25519.getSharedSecret(newX25519PrivateKey, mxePublicKey));
+    const proofNonce = new anchor.BN(ticket.ownerProofNonce.toString()).toArrayLike(Buffer, "le", 16);
+    const [tag] = cipher.decrypt([ticket.ownerProof], proofNonce);
+    log(`  Ownership proof under new key: ${tag === BigInt(ticketOffset.toString()) ? '✓' : '✗'}\n`);
+    expect(tag.toString()).to.equal(ticketOffset.toString());
+  });
+
+  it("Should move DC between a wallet's own sub-accounts without variance", async () => {
+    logBox("TEST 16: SUB-ACCOUNTS");
+
+    const user = users[3];
+    const [subAccount] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dc_user_token_account"),
+        user.keypair.publicKey.toBuffer(),
+        new anchor.BN(1).toArrayLike(Buffer, "le", 2),
+      ],
+      program.programId
+    );
+    const savings = { ...user, dcTokenAccount: subAccount };
+
+    logSection("Create sub-account #1");
+    const createOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .queueCreateDcTokenAccount(
+        createOffset,
+        Array.from(user.x25519PublicKey),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString()),
+        1
+      )
+      .accountsPartial({
+        ...queueAccounts(createOffset, "init_user_dc_balance"),
+        payer: user.keypair.publicKey,
+        dcUserTokenAccount: subAccount,
+      })
+      .signers([user.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const created = await program.account.dcUserTokenAccount.fetch(subAccount, "confirmed");
+    expect(created.index).to.equal(1);
+    expect(created.owner.toBase58()).to.equal(user.keypair.publicKey.toBase58());
+
+    async function subAccountTransfer(
+      from: PublicKey,
+      to: PublicKey,
+      amount: number
+    ) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const [receiptPDA] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dc_operation_receipt"),
+          user.keypair.publicKey.toBuffer(),
+          computationOffset.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const eventPromise = awaitEvent("subAccountTransferEvent");
+      await program.methods
+        .queueSubAccountTransfer(computationOffset, new anchor.BN(amount))
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "sub_account_transfer"),
+          payer: user.keypair.publicKey,
+          dcUserTokenAccount: from,
+          receiverDcUserTokenAccount: to,
+          operationReceipt: receiptPDA,
+        })
+        .signers([user.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const receipt = await program.account.dcOperationReceipt.fetch(receiptPDA);
+      return { event, receipt };
+    }
+
+    logSection("Primary → sub-account #1");
+    const amount = 5 * 1_000_000;
+    const primaryBefore = BigInt(await getDecryptedBalance(user));
+    const moved = await subAccountTransfer(user.dcTokenAccount, subAccount, amount);
+    const primaryAfter = BigInt(await getDecryptedBalance(user));
+    const savingsAfter = BigInt(await getDecryptedBalance(savings));
+    log(`  Primary: ${primaryBefore} → ${primaryAfter}`);
+    log(`  Sub-account #1: ${savingsAfter}`);
+    expect(moved.event.status).to.deep.equal({ success: {} });
+    expect(moved.receipt.operation).to.deep.equal({ subAccountTransfer: {} });
+    expect(moved.receipt.status).to.deep.equal({ succeeded: {} });
+    expect(primaryBefore - primaryAfter).to.equal(BigInt(amount));
+    expect(savingsAfter).to.equal(BigInt(amount));
+
+    logSection("Sub-account #1 → primary, more than its balance");
+    const overdrawn = await subAccountTransfer(subAccount, user.dcTokenAccount, amount + 1);
+    log(`  Status: ${Object.keys(overdrawn.event.status)[0]}`);
+    expect(overdrawn.event.status).to.deep.equal({ insufficientFunds: {} });
+    expect(overdrawn.receipt.status).to.deep.equal({ failed: {} });
+    expect(BigInt(await getDecryptedBalance(savings))).to.equal(BigInt(amount));
+    expect(BigInt(await getDecryptedBalance(user))).to.equal(primaryAfter);
+
+    logSection("Same sub-account on both sides");
+    let rejected = false;
+    try {
+      await subAccountTransfer(subAccount, subAccount, 1);
+    } catch (e) {
+      rejected = e.toString().includes("SubAccountTransferToSelf");
+    }
+    log(`  Rejected: ${rejected ? '✓' : '✗'}\n`);
+    expect(rejected).to.equal(true);
+  });
+
+  it("Should let a wallet without a DC account claim a transfer, and the sender reclaim it after expiry", async () => {
+    logBox("TEST 17: CLAIMABLE TRANSFERS");
+
+    const sender = users[0];
+    const amount = 2 * 1_000_000;
+
+    const keypair = anchor.web3.Keypair.generate();
+    const airdropSig = await program.provider.connection.requestAirdrop(
+      keypair.publicKey,
+      1_000_000_000
+    );
+    await program.provider.connection.confirmTransaction(airdropSig);
+    const x25519PrivateKey = nacl.sign.detached(Buffer.from("dgn.cash"), keypair.secretKey).slice(0, 32);
+    const [dcTokenAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_user_token_account"), keypair.publicKey.toBuffer()],
+      program.programId
+    );
+    const recipient: UserData = {
+      keypair,
+      x25519PrivateKey,
+      x25519PublicKey: x25519.getPublicKey(x25519PrivateKey),
+      ata: getAssociatedTokenAddressSync(depositMint, keypair.publicKey),
+      dcTokenAccount,
+    };
+
+    async function sendClaimable(claimWindow: number) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const [pendingClaim] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dc_pending_claim"),
+          recipient.keypair.publicKey.toBuffer(),
+          computationOffset.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const eventPromise = awaitEvent("claimableTransferSentEvent");
+      await program.methods
+        .queueSendClaimableTransfer(
+          computationOffset,
+          new anchor.BN(amount),
+          0,
+          recipient.keypair.publicKey,
+          new anchor.BN(claimWindow)
+        )
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "send_claimable_transfer"),
+          payer: sender.keypair.publicKey,
+          dcUserTokenAccount: sender.dcTokenAccount,
+          dcGlobalMintAccount: dcGlobalMintAddress(),
+          depositAta: getAssociatedTokenAddressSync(depositMint, dcGlobalMintAddress(), true),
+          pendingClaim,
+        })
+        .signers([sender.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      expect(event.status).to.deep.equal({ success: {} });
+      return pendingClaim;
+    }
+
+    async function claim(method: "queueClaimTransfer" | "queueReclaimTransfer", claimer: UserData, pendingClaim: PublicKey) {
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const eventPromise = awaitEvent("transferClaimedEvent");
+      await program.methods[method](computationOffset)
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "claim_transfer"),
+          payer: claimer.keypair.publicKey,
+          pendingClaim,
+          dcUserTokenAccount: claimer.dcTokenAccount,
+        })
+        .signers([claimer.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      return event;
+    }
+
+    async function rejectedWith(error: string, attempt: () => Promise<unknown>) {
+      try {
+        await attempt();
+      } catch (e) {
+        return e.toString().includes(error);
+      }
+      return false;
+    }
+
+    logSection("Send to a wallet without a DC account");
+    const senderBefore = BigInt(await getDecryptedBalance(sender));
+    const pendingClaim = await sendClaimable(600);
+    const claimAccount = await program.account.dcPendingClaim.fetch(pendingClaim);
+    expect(claimAccount.status).to.deep.equal({ claimable: {} });
+    expect(claimAccount.recipient.toBase58()).to.equal(recipient.keypair.publicKey.toBase58());
+    // The sender is charged like a transfer, so the fee comes on top of the amount
+    const senderDebit = senderBefore - BigInt(await getDecryptedBalance(sender));
+    expect(senderDebit >= BigInt(amount)).to.be.true;
+    log(`  Sender debited ${senderDebit} for ${amount}`);
+    log(`  Pending claim: ${pendingClaim.toBase58()}`);
+
+    const earlyReclaim = await rejectedWith("PendingClaimNotExpired", () =>
+      claim("queueReclaimTransfer", sender, pendingClaim)
+    );
+    log(`  Early reclaim rejected: ${earlyReclaim ? '✓' : '✗'}`);
+    expect(earlyReclaim).to.equal(true);
+
+    logSection("Recipient opens a DC account and claims");
+    const createOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .queueCreateDcTokenAccount(
+        createOffset,
+        Array.from(recipient.x25519PublicKey),
+        new anchor.BN(deserializeLE(randomBytes(16)).toString()),
+        0
+      )
+      .accountsPartial({
+        ...queueAccounts(createOffset, "init_user_dc_balance"),
+        payer: recipient.keypair.publicKey,
+        dcUserTokenAccount: recipient.dcTokenAccount,
+      })
+      .signers([recipient.keypair])
+      .rpc({ skipPreflight: false, commitment: "confirmed" });
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const claimed = await claim("queueClaimTransfer", recipient, pendingClaim);
+    const recipientBalance = await getDecryptedBalance(recipient);
+    log(`  Claimed: ${recipientBalance}`);
+    expect(claimed.status).to.deep.equal({ success: {} });
+    expect(recipientBalance).to.equal(amount.toString());
+    expect(await program.provider.connection.getAccountInfo(pendingClaim)).to.equal(null);
+
+    logSection("Sender reclaims an expired claim");
+    const expiring = await sendClaimable(2);
+    const reclaimBefore = BigInt(await getDecryptedBalance(sender));
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const lateClaim = await rejectedWith("PendingClaimExpired", () =>
+      claim("queueClaimTransfer", recipient, expiring)
+    );
+    log(`  Late claim rejected: ${lateClaim ? '✓' : '✗'}`);
+    expect(lateClaim).to.equal(true);
+
+    const reclaimed = await claim("queueReclaimTransfer", sender, expiring);
+    const reclaimAfter = BigInt(await getDecryptedBalance(sender));
+    log(`  Reclaimed: ${reclaimAfter - reclaimBefore}\n`);
+    expect(reclaimed.status).to.deep.equal({ success: {} });
+    expect(reclaimAfter - reclaimBefore).to.equal(BigInt(amount));
+    expect(await program.provider.connection.getAccountInfo(expiring)).to.equal(null);
+  });
+
+  it("Should transfer an encrypted amount and reveal only the status", async () => {
+    logBox("TEST 18: CONFIDENTIAL TRANSFERS");
+
+    const sender = users[1];
+    const receiver = users[2];
+    const [dcConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dc_config")],
+      program.programId
+    );
+    const { maxTransferAmount } = await program.account.dcConfig.fetch(dcConfigPDA, "confirmed");
+
+    async function confidentialTransfer(amount: bigint) {
+      // Amount is encrypted under the sender's DC account key, only the MXE can read it
+      const nonce = randomBytes(16);
+      const cipher = new RescueCipher(x25519.getSharedSecret(sender.x25519PrivateKey, mxePublicKey));
+      const [ciphertext] = cipher.encrypt([amount], nonce);
+
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      const eventPromise = awaitEvent("confidentialTransferEvent");
+      await program.methods
+        .queueConfidentialTransfer(
+          computationOffset,
+          ciphertext,
+          new anchor.BN(deserializeLE(nonce).toString()),
+          0,
+          receiver.keypair.publicKey
+        )
+        .accountsPartial({
+          ...queueAccounts(computationOffset, "confidential_transfer"),
+          payer: sender.keypair.publicKey,
+          dcGlobalMintAccount: dcGlobalMintAddress(),
+          dcUserTokenAccount: sender.dcTokenAccount,
+          receiverDcUserTokenAccount: receiver.dcTokenAccount,
+          depositAta: getAssociatedTokenAddressSync(depositMint, dcGlobalMintAddress(), true),
+        })
+        .signers([sender.keypair])
+        .rpc({ skipPreflight: false, commitment: "confirmed" });
+      const event = await eventPromise;
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      const [receipt] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dc_operation_receipt"),
+          sender.keypair.publicKey.toBuffer(),
+          computationOffset.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      return { event, receipt: await program.account.dcOperationReceipt.fetch(receipt, "confirmed") };
+    }
+
+    logSection("Encrypted amount");
+    const amount = 10n * 1_000_000n;
+    const senderBefore = BigInt(await getDecryptedBalance(sender));
+    const receiverBefore = BigInt(await getDecryptedBalance(receiver));
+    const sent = await confidentialTransfer(amount);
+    const senderAfter = BigInt(await getDecryptedBalance(sender));
+    const receiverAfter = BigInt(await getDecryptedBalance(receiver));
+
+    log(`  Sender:   ${Number(senderBefore) / 1_000_000} → ${Number(senderAfter) / 1_000_000}`);
+    log(`  Receiver: ${Number(receiverBefore) / 1_000_000} → ${Number(receiverAfter) / 1_000_000}`);
+    log(`  Event fields: ${Object.keys(sent.event).join(", ")}`);
+    expect(sent.event.status).to.deep.equal({ success: {} });
+    expect(sent.event).to.not.have.property("transferAmount");
+    expect(sent.receipt.operation).to.deep.equal({ confidentialTransfer: {} });
+    expect(sent.receipt.amount.toNumber()).to.equal(0);
+    expect(receiverAfter - receiverBefore).to.equal(amount);
+    expect(senderAfter < senderBefore).to.equal(true);
+
+    logSection("Amount above the max, checked in MPC");
+    const oversized = await confidentialTransfer(BigInt(maxTransferAmount.toString()) + 1n);
+    log(`  Status: ${Object.keys(oversized.event.status)[0]}\n`);
+    expect(oversized.event.status).to.deep.equal({ maxAmountExceeded: {} });
+    expect(oversized.receipt.status).to.deep.equal({ failed: {} });
+    expect(BigInt(await getDecryptedBalance(sender))).to.equal(senderAfter);
+    expect(BigInt(await getDecryptedBalance(receiver))).to.equal(receiverAfter);
+  });
+
+  it("Should show final balances", async () => {
+    logBox("FINAL BALANCES");
+
+    for (let i = 0; i < users.length; i++) {
+      const user = users[i];
+      const dcBalance = await getDecryptedBalance(user);
+      const splBalance = await getSPLBalance(user.ata);
+
+      log(`User ${i + 1}:`);
+      log(`  DC:   ${(Number(dcBalance) / 1_000_000).toFixed(2)} DC`);
+      log(`  USDC: ${(Number(splBalance) / 1_000_000).toFixed(2)} USDC`);
+      log(``);
+    }
+  });
+
+  async function initTransferCompDef(
+    program: Program<DegenCash>,
+    owner: anchor.web3.Keypair
+  ): Promise<string> {
+    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+      "ComputationDefinitionAccount"
+    );
+    const offset = getCompDefAccOffset("transfer");
+
+    const compDefPDA = PublicKey.findProgramAddressSync(
+      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
+      getArciumProgAddress()
+    )[0];
+
+    const sig = await program.methods
+      .initTransferCompDef()
+      .accounts({
+        compDefAccount: compDefPDA,
+        payer: owner.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    return sig;
+  }
+
+  async function initWithdrawCompDef(
+    program: Program<DegenCash>,
+    owner: anchor.web3.Keypair
+  ): Promise<string> {
+    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+      "ComputationDefinitionAccount"
+    );
+    const offset = getCompDefAccOffset("withdraw");
+
+    const compDefPDA = PublicKey.findProgramAddressSync(
+      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
+      getArciumProgAddress()
+    )[0];
+
+    const sig = await program.methods
+      .initWithdrawCompDef()
+      .accounts({
+        compDefAccount: compDefPDA,
+        payer: owner.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    return sig;
+  }
+
+  async function initGlobalDCMintCompDef(
+    program: Program<DegenCash>,
+    owner: anchor.web3.Keypair
+  ): Promise<string> {
+    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+      "ComputationDefinitionAccount"
+    );
+    const offset = getCompDefAccOffset("init_global_dc_mint");
+
+    const compDefPDA = PublicKey.findProgramAddressSync(
+      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
+      getArciumProgAddress()
+    )[0];
+
+    const sig = await program.methods
+      .initGlobalDcMintCompDef()
+      .accounts({
+        compDefAccount: compDefPDA,
+        payer: owner.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    return sig;
+  }
+
+  async function initDepositCompDef(
+    program: Program<DegenCash>,
+    owner: anchor.web3.Keypair
+  ): Promise<string> {
+    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+      "ComputationDefinitionAccount"
+    );
+    const offset = getCompDefAccOffset("deposit");
+
+    const compDefPDA = PublicKey.findProgramAddressSync(
+      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
+      getArciumProgAddress()
+    )[0];
+
+    const sig = await program.methods
+      .initDepositCompDef()
+      .accounts({
+        compDefAccount: compDefPDA,
+        payer: owner.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    return sig;
+  }
+
+  async function initCreateDcTokenAccountCompDef(
+    program: Program<DegenCash>,
+    owner: anchor.web3.Keypair
+  ): Promise<string> {
+    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+      "ComputationDefinitionAccount"
+    );
+    const offset = getCompDefAccOffset("init_user_dc_balance");
 
     const compDefPDA = PublicKey.findProgramAddressSync(
       [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
@@ -618,4 +2331,8 @@ function readKpJson(path: string): anchor.web3.Keypair {
   return anchor.web3.Keypair.fromSecretKey(
     new Uint8Array(JSON.parse(file.toString()))
   );
-}
\ No newline at end of file
+}
+// Mirrors claimer_tag in the program: first 16 bytes of the wallet as a u128 (LE)
+function claimerTag(wallet: PublicKey): bigint {
+  return deserializeLE(wallet.toBytes().slice(0, 16));
+}